@@ -3,15 +3,6 @@ use anchor_spl::token::{self, Token, TokenAccount, Transfer};
 
 declare_id!("mkt1exxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxx");
 
-// Oracle price account structure (must match oracle program)
-#[account]
-pub struct PriceAccount {
-    pub commodity: [u8; 32],
-    pub price: u64,
-    pub confidence: u64,
-    pub timestamp: i64,
-}
-
 #[program]
 pub mod prediction_market {
     use super::*;
@@ -128,15 +119,9 @@ pub mod prediction_market {
         );
         require!(!market.resolved, ErrorCode::AlreadyResolved);
         
-        // Deserialize oracle price data
-        let oracle_data = ctx.accounts.price_oracle.try_borrow_data()?;
-        let price_account = PriceAccount::try_deserialize(&mut &oracle_data[..])?;
-        
         // Check oracle price is not stale
-        require!(
-            clock.unix_timestamp - price_account.timestamp < 3600,
-            ErrorCode::StaleOraclePrice
-        );
+        let price_account = &ctx.accounts.price_oracle;
+        require!(!price_account.is_stale()?, ErrorCode::StaleOraclePrice);
         
         // Get oracle data
         let actual_price = price_account.price; // in cents: 247 = $2.47
@@ -301,11 +286,15 @@ pub struct ResolveMarket<'info> {
     #[account(mut)]
     pub market: Account<'info, PredictionMarket>,
     
-    /// CHECK: Oracle price account from oracle program - validated by deserializing
-    pub price_oracle: AccountInfo<'info>,
+    /// Oracle feed for the market's commodity
+    #[account(
+        seeds = [b"price", market.commodity.as_ref()],
+        bump = price_oracle.bump,
+        seeds::program = oracle_program.key()
+    )]
+    pub price_oracle: Account<'info, oracle::PriceAccount>,
     
-    /// CHECK: Oracle program ID
-    pub oracle_program: AccountInfo<'info>,
+    pub oracle_program: Program<'info, oracle::program::Oracle>,
     
     pub authority: Signer<'info>,
 }
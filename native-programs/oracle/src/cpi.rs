@@ -0,0 +1,73 @@
+//! Helpers for programs reading the oracle through CPI
+
+use borsh::BorshDeserialize;
+use solana_program::{
+    account_info::AccountInfo,
+    program::{get_return_data, invoke},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+use crate::{error::OracleError, instruction, state::PriceFeed};
+
+/// Invoke `GetPrice` on `price_feed` and decode the returned `PriceFeed`.
+/// Fails if the oracle rejects the feed or its price is older than `max_age` seconds.
+pub fn get_price<'a>(
+    oracle_program: &AccountInfo<'a>,
+    price_feed: &AccountInfo<'a>,
+    max_age: i64,
+) -> Result<PriceFeed, ProgramError> {
+    let instruction = instruction::get_price(oracle_program.key, price_feed.key, max_age);
+    invoke(&instruction, &[price_feed.clone(), oracle_program.clone()])?;
+    decode_price(oracle_program.key, get_return_data())
+}
+
+/// Decode the `PriceFeed` returned by `GetPrice`
+fn decode_price(
+    oracle_program: &Pubkey,
+    return_data: Option<(Pubkey, Vec<u8>)>,
+) -> Result<PriceFeed, ProgramError> {
+    // Return data is only trusted if the oracle itself set it
+    match return_data {
+        Some((program_id, data)) if program_id == *oracle_program => {
+            PriceFeed::try_from_slice(&data).map_err(|_| ProgramError::InvalidAccountData)
+        }
+        _ => Err(OracleError::PriceNotReturned.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::processor::price_return_data;
+
+    #[test]
+    fn test_decode_price_only_from_oracle_return_data() {
+        let oracle_program = Pubkey::new_unique();
+        let price_feed = PriceFeed {
+            commodity: [1u8; 32],
+            price: 15000,
+            confidence: 100,
+            timestamp: 1_000,
+            decimals: 2,
+        };
+        let data = price_return_data(&price_feed, 1_060, 60).unwrap();
+
+        let decoded = decode_price(&oracle_program, Some((oracle_program, data.clone()))).unwrap();
+        assert_eq!((decoded.price, decoded.timestamp), (15000, 1_000));
+
+        // Set by another program further down the call stack, or not at all
+        assert_eq!(
+            decode_price(&oracle_program, Some((Pubkey::new_unique(), data))).unwrap_err(),
+            OracleError::PriceNotReturned.into()
+        );
+        assert_eq!(
+            decode_price(&oracle_program, None).unwrap_err(),
+            OracleError::PriceNotReturned.into()
+        );
+        assert_eq!(
+            decode_price(&oracle_program, Some((oracle_program, vec![1, 2, 3]))).unwrap_err(),
+            ProgramError::InvalidAccountData
+        );
+    }
+}
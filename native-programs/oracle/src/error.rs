@@ -0,0 +1,111 @@
+//! Error types
+
+use num_derive::FromPrimitive;
+use num_traits::FromPrimitive;
+#[allow(deprecated)]
+use solana_program::{
+    decode_error::DecodeError,
+    instruction::InstructionError,
+    msg,
+    program_error::{PrintProgramError, ProgramError},
+};
+use thiserror::Error;
+
+/// Errors that may be returned by the oracle program
+#[derive(Clone, Copy, Debug, Eq, Error, FromPrimitive, PartialEq)]
+pub enum OracleError {
+    /// 0 - Oracle state has not been initialized
+    #[error("Oracle is not initialized")]
+    NotInitialized,
+    /// 1 - Oracle state already exists
+    #[error("Oracle is already initialized")]
+    AlreadyInitialized,
+    /// 2 - Signer is not the oracle authority
+    #[error("Signer is not the oracle authority")]
+    Unauthorized,
+    /// 3 - Oracle state account is not the `[b"oracle_state"]` PDA
+    #[error("Oracle state address does not match its PDA")]
+    InvalidOracleState,
+    /// 4 - Price feed account is not the `[b"price", commodity]` PDA
+    #[error("Price feed address does not match the commodity")]
+    PriceFeedMismatch,
+    /// 5 - Price is older than the caller's maximum age
+    #[error("Price is stale")]
+    StalePrice,
+    /// 6 - Negative maximum price age
+    #[error("Maximum price age must not be negative")]
+    InvalidMaxAge,
+    /// 7 - `GetPrice` returned no price data to the CPI caller
+    #[error("Oracle returned no price data")]
+    PriceNotReturned,
+    /// 8 - Account is not the oracle program's upgradeable loader ProgramData
+    #[error("Program data account does not belong to the oracle program")]
+    InvalidProgramData,
+    /// 9 - Initializer is not the oracle program's upgrade authority
+    #[error("Signer is not the program upgrade authority")]
+    NotUpgradeAuthority,
+}
+
+impl From<OracleError> for ProgramError {
+    fn from(e: OracleError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
+
+#[allow(deprecated)]
+impl<T> DecodeError<T> for OracleError {
+    fn type_of() -> &'static str {
+        "OracleError"
+    }
+}
+
+#[allow(deprecated)]
+impl PrintProgramError for OracleError {
+    fn print<E>(&self)
+    where
+        E: 'static + std::error::Error + DecodeError<E> + PrintProgramError + FromPrimitive,
+    {
+        msg!("Error: {}", self);
+    }
+}
+
+impl OracleError {
+    /// Decode a custom error code returned by the oracle
+    pub fn from_code(code: u32) -> Option<Self> {
+        Self::from_u32(code)
+    }
+
+    /// Decode the oracle error behind a program error, for clients and CPI callers
+    pub fn from_program_error(error: &ProgramError) -> Option<Self> {
+        match error {
+            ProgramError::Custom(code) => Self::from_code(*code),
+            _ => None,
+        }
+    }
+
+    /// Decode the oracle error of a failed transaction's instruction
+    pub fn from_instruction_error(error: &InstructionError) -> Option<Self> {
+        match error {
+            InstructionError::Custom(code) => Self::from_code(*code),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_codes_round_trip() {
+        let error: ProgramError = OracleError::StalePrice.into();
+        assert_eq!(error, ProgramError::Custom(5));
+        assert_eq!(OracleError::from_program_error(&error), Some(OracleError::StalePrice));
+        assert_eq!(
+            OracleError::from_instruction_error(&InstructionError::Custom(4)),
+            Some(OracleError::PriceFeedMismatch)
+        );
+        assert_eq!(OracleError::from_program_error(&ProgramError::InvalidArgument), None);
+        assert_eq!(OracleError::from_code(100), None);
+    }
+}
@@ -0,0 +1,130 @@
+//! Instruction types and builders
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+use solana_loader_v3_interface::get_program_data_address;
+use solana_system_interface::program as system_program;
+
+use crate::state::{find_oracle_state_address, find_price_feed_address};
+
+/// Instruction enum
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
+pub enum OracleInstruction {
+    /// Initialize oracle, creating the oracle state PDA. Only the program's upgrade
+    /// authority may initialize, so the deployment can't be front-run.
+    /// Accounts:
+    /// 0. `[writable, signer]` Authority account, the program's upgrade authority;
+    ///    pays for the oracle state
+    /// 1. `[writable]` Oracle state PDA `[b"oracle_state"]`
+    /// 2. `[]` System program
+    /// 3. `[]` Program data account of the oracle program (upgradeable loader)
+    Initialize,
+
+    /// Update price feed, creating the feed PDA on its first update
+    /// Accounts:
+    /// 0. `[writable, signer]` Authority account, pays for a new price feed
+    /// 1. `[]` Oracle state PDA `[b"oracle_state"]`
+    /// 2. `[writable]` Price feed PDA `[b"price", commodity]`
+    /// 3. `[]` System program
+    UpdatePrice {
+        commodity: [u8; 32],
+        price: u64,
+        confidence: u64,
+        decimals: u8,
+    },
+
+    /// Get price (read-only, for CPI). Fails if the price is older than
+    /// `max_age` seconds, otherwise returns the Borsh-encoded `PriceFeed`
+    /// as return data; see `cpi::get_price`.
+    /// Accounts:
+    /// 0. `[]` Price feed PDA `[b"price", commodity]`
+    GetPrice {
+        max_age: i64,
+    },
+}
+
+/// Creates an `Initialize` instruction with `authority` as the oracle authority
+pub fn initialize(program_id: &Pubkey, authority: &Pubkey) -> Instruction {
+    Instruction::new_with_borsh(
+        *program_id,
+        &OracleInstruction::Initialize,
+        vec![
+            AccountMeta::new(*authority, true),
+            AccountMeta::new(find_oracle_state_address(program_id).0, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(get_program_data_address(program_id), false),
+        ],
+    )
+}
+
+/// Creates an `UpdatePrice` instruction for the feed PDA of `commodity`
+pub fn update_price(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    commodity: [u8; 32],
+    price: u64,
+    confidence: u64,
+    decimals: u8,
+) -> Instruction {
+    Instruction::new_with_borsh(
+        *program_id,
+        &OracleInstruction::UpdatePrice {
+            commodity,
+            price,
+            confidence,
+            decimals,
+        },
+        vec![
+            AccountMeta::new(*authority, true),
+            AccountMeta::new_readonly(find_oracle_state_address(program_id).0, false),
+            AccountMeta::new(find_price_feed_address(program_id, &commodity).0, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    )
+}
+
+/// Creates a `GetPrice` instruction reading `price_feed`
+pub fn get_price(program_id: &Pubkey, price_feed: &Pubkey, max_age: i64) -> Instruction {
+    Instruction::new_with_borsh(
+        *program_id,
+        &OracleInstruction::GetPrice { max_age },
+        vec![AccountMeta::new_readonly(*price_feed, false)],
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_update_price_instruction() {
+        let program_id = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+        let mut commodity = [0u8; 32];
+        commodity[..5].copy_from_slice(b"MAIZE");
+
+        let instruction = update_price(&program_id, &authority, commodity, 15000, 100, 2);
+        assert_eq!(instruction.program_id, program_id);
+        assert_eq!(
+            instruction.accounts.iter().map(|meta| meta.pubkey).collect::<Vec<_>>(),
+            vec![
+                authority,
+                find_oracle_state_address(&program_id).0,
+                find_price_feed_address(&program_id, &commodity).0,
+                system_program::id(),
+            ]
+        );
+        assert!(instruction.accounts[0].is_signer && instruction.accounts[0].is_writable);
+        assert!(!instruction.accounts[1].is_writable && instruction.accounts[2].is_writable);
+
+        match OracleInstruction::try_from_slice(&instruction.data).unwrap() {
+            OracleInstruction::UpdatePrice { commodity: decoded, price, .. } => {
+                assert_eq!((decoded, price), (commodity, 15000));
+            }
+            other => panic!("unexpected instruction: {:?}", other),
+        }
+    }
+}
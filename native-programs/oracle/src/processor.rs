@@ -0,0 +1,512 @@
+//! Program instruction processor
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    program::{invoke_signed, set_return_data},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    clock::Clock,
+    rent::Rent,
+    sysvar::Sysvar,
+};
+use solana_loader_v3_interface::{get_program_data_address, state::UpgradeableLoaderState};
+use solana_system_interface::{instruction as system_instruction, program as system_program};
+
+use crate::{
+    error::OracleError,
+    instruction::OracleInstruction,
+    state::{
+        find_oracle_state_address, find_price_feed_address, OracleState, PriceFeed, ORACLE_STATE_SEED,
+        PRICE_SEED,
+    },
+};
+
+/// Processes an `OracleInstruction`
+pub fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let instruction = OracleInstruction::try_from_slice(instruction_data)
+        .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+    match instruction {
+        OracleInstruction::Initialize => {
+            msg!("Instruction: Initialize");
+            initialize(program_id, accounts)
+        }
+        OracleInstruction::UpdatePrice {
+            commodity,
+            price,
+            confidence,
+            decimals,
+        } => {
+            msg!("Instruction: UpdatePrice");
+            update_price(program_id, accounts, commodity, price, confidence, decimals)
+        }
+        OracleInstruction::GetPrice { max_age } => {
+            msg!("Instruction: GetPrice");
+            get_price(program_id, accounts, max_age)
+        }
+    }
+}
+
+fn initialize(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let authority_info = next_account_info(account_info_iter)?;
+    let oracle_state_info = next_account_info(account_info_iter)?;
+    let system_program_info = next_account_info(account_info_iter)?;
+    let program_data_info = next_account_info(account_info_iter)?;
+
+    // Verify authority is signer
+    if !authority_info.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Only the upgrade authority may claim the oracle, so nobody can front-run it
+    if upgrade_authority(program_id, program_data_info)? != Some(*authority_info.key) {
+        return Err(OracleError::NotUpgradeAuthority.into());
+    }
+
+    // Verify oracle state is the program's PDA
+    let (oracle_state_key, bump) = find_oracle_state_address(program_id);
+    if *oracle_state_info.key != oracle_state_key {
+        return Err(OracleError::InvalidOracleState.into());
+    }
+
+    // Only a PDA that doesn't exist yet can be initialized
+    if oracle_state_info.owner == program_id {
+        return Err(OracleError::AlreadyInitialized.into());
+    }
+    create_pda_account(
+        authority_info,
+        oracle_state_info,
+        system_program_info,
+        program_id,
+        OracleState::LEN,
+        &[ORACLE_STATE_SEED, &[bump]],
+    )?;
+
+    // Initialize state
+    let oracle_state = OracleState {
+        authority: *authority_info.key,
+        is_initialized: true,
+    };
+
+    // Serialize back
+    oracle_state.serialize(&mut &mut oracle_state_info.data.borrow_mut()[..])?;
+
+    msg!("Oracle initialized with authority: {}", authority_info.key);
+    Ok(())
+}
+
+/// Reads the upgrade authority from the program's ProgramData account
+fn upgrade_authority(
+    program_id: &Pubkey,
+    program_data_info: &AccountInfo,
+) -> Result<Option<Pubkey>, ProgramError> {
+    if *program_data_info.key != get_program_data_address(program_id)
+        || *program_data_info.owner != solana_sdk_ids::bpf_loader_upgradeable::id()
+    {
+        return Err(OracleError::InvalidProgramData.into());
+    }
+
+    let data = program_data_info.data.borrow();
+    let metadata = data
+        .get(..UpgradeableLoaderState::size_of_programdata_metadata())
+        .ok_or(OracleError::InvalidProgramData)?;
+    match bincode::deserialize(metadata) {
+        Ok(UpgradeableLoaderState::ProgramData {
+            upgrade_authority_address,
+            ..
+        }) => Ok(upgrade_authority_address),
+        _ => Err(OracleError::InvalidProgramData.into()),
+    }
+}
+
+fn update_price(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    commodity: [u8; 32],
+    price: u64,
+    confidence: u64,
+    decimals: u8,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let authority_info = next_account_info(account_info_iter)?;
+    let oracle_state_info = next_account_info(account_info_iter)?;
+    let price_feed_info = next_account_info(account_info_iter)?;
+    let system_program_info = next_account_info(account_info_iter)?;
+
+    // Verify authority is signer
+    if !authority_info.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Verify oracle state
+    if *oracle_state_info.key != find_oracle_state_address(program_id).0 {
+        return Err(OracleError::InvalidOracleState.into());
+    }
+    if oracle_state_info.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    let oracle_state = OracleState::try_from_slice(&oracle_state_info.data.borrow())?;
+    if !oracle_state.is_initialized {
+        return Err(OracleError::NotInitialized.into());
+    }
+    if oracle_state.authority != *authority_info.key {
+        return Err(OracleError::Unauthorized.into());
+    }
+
+    // Verify price feed is the PDA of this commodity, creating it on first use
+    let (price_feed_key, bump) = find_price_feed_address(program_id, &commodity);
+    if *price_feed_info.key != price_feed_key {
+        return Err(OracleError::PriceFeedMismatch.into());
+    }
+    if price_feed_info.owner != program_id {
+        create_pda_account(
+            authority_info,
+            price_feed_info,
+            system_program_info,
+            program_id,
+            PriceFeed::LEN,
+            &[PRICE_SEED, &commodity, &[bump]],
+        )?;
+    }
+
+    // Get current timestamp
+    let clock = Clock::get()?;
+    let timestamp = clock.unix_timestamp;
+
+    // Create/update price feed
+    let price_feed = PriceFeed {
+        commodity,
+        price,
+        confidence,
+        timestamp,
+        decimals,
+    };
+
+    // Serialize to account
+    price_feed.serialize(&mut &mut price_feed_info.data.borrow_mut()[..])?;
+
+    // Convert commodity bytes to string for logging (up to first null)
+    let commodity_name = String::from_utf8_lossy(
+        &commodity[..commodity.iter().position(|&c| c == 0).unwrap_or(32)]
+    );
+    msg!("Price updated for {}: {} (confidence: {}, timestamp: {})", 
+         commodity_name, price, confidence, timestamp);
+
+    Ok(())
+}
+
+/// Create a program-owned PDA funded by `payer`. An address that was already
+/// sent lamports is topped up to rent exemption, allocated and assigned instead.
+fn create_pda_account<'a>(
+    payer: &AccountInfo<'a>,
+    new_account: &AccountInfo<'a>,
+    system_program_info: &AccountInfo<'a>,
+    program_id: &Pubkey,
+    space: usize,
+    seeds: &[&[u8]],
+) -> ProgramResult {
+    if !system_program::check_id(system_program_info.key) {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    if !system_program::check_id(new_account.owner) {
+        return Err(ProgramError::IllegalOwner);
+    }
+
+    let required_lamports = Rent::get()?.minimum_balance(space);
+    let accounts = [payer.clone(), new_account.clone(), system_program_info.clone()];
+    if new_account.lamports() == 0 {
+        return invoke_signed(
+            &system_instruction::create_account(
+                payer.key,
+                new_account.key,
+                required_lamports,
+                space as u64,
+                program_id,
+            ),
+            &accounts,
+            &[seeds],
+        );
+    }
+
+    let top_up = required_lamports.saturating_sub(new_account.lamports());
+    if top_up > 0 {
+        invoke_signed(
+            &system_instruction::transfer(payer.key, new_account.key, top_up),
+            &accounts,
+            &[],
+        )?;
+    }
+    invoke_signed(
+        &system_instruction::allocate(new_account.key, space as u64),
+        &accounts[1..],
+        &[seeds],
+    )?;
+    invoke_signed(
+        &system_instruction::assign(new_account.key, program_id),
+        &accounts[1..],
+        &[seeds],
+    )
+}
+
+fn get_price(program_id: &Pubkey, accounts: &[AccountInfo], max_age: i64) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let price_feed_info = next_account_info(account_info_iter)?;
+
+    if max_age < 0 {
+        return Err(OracleError::InvalidMaxAge.into());
+    }
+
+    // Only the oracle's own feed PDAs carry prices
+    if price_feed_info.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    // Deserialize price feed
+    let price_feed = PriceFeed::try_from_slice(&price_feed_info.data.borrow())?;
+    if *price_feed_info.key != find_price_feed_address(program_id, &price_feed.commodity).0 {
+        return Err(OracleError::PriceFeedMismatch.into());
+    }
+
+    // Return price data (for CPI callers to read) unless it is stale
+    let clock = Clock::get()?;
+    set_return_data(&price_return_data(&price_feed, clock.unix_timestamp, max_age)?);
+    msg!("Price: {}, Confidence: {}, Timestamp: {}", 
+         price_feed.price, price_feed.confidence, price_feed.timestamp);
+
+    Ok(())
+}
+
+/// `GetPrice` return data: the serialized feed, if its price is no older than `max_age` at `now`
+pub(crate) fn price_return_data(
+    price_feed: &PriceFeed,
+    now: i64,
+    max_age: i64,
+) -> Result<Vec<u8>, ProgramError> {
+    if price_feed.is_stale(now, max_age) {
+        return Err(OracleError::StalePrice.into());
+    }
+    Ok(borsh::to_vec(price_feed)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Owned storage behind a fabricated `AccountInfo`
+    struct TestAccount {
+        key: Pubkey,
+        owner: Pubkey,
+        lamports: u64,
+        data: Vec<u8>,
+    }
+
+    impl TestAccount {
+        fn new(owner: &Pubkey, data: Vec<u8>) -> Self {
+            Self {
+                key: Pubkey::new_unique(),
+                owner: *owner,
+                lamports: 1_000_000,
+                data,
+            }
+        }
+
+        fn info(&mut self, is_signer: bool) -> AccountInfo<'_> {
+            AccountInfo::new(
+                &self.key,
+                is_signer,
+                true,
+                &mut self.lamports,
+                &mut self.data,
+                &self.owner,
+                false,
+                0,
+            )
+        }
+    }
+
+    fn program_data(program_id: &Pubkey, upgrade_authority: Option<Pubkey>) -> TestAccount {
+        let mut data = bincode::serialize(&UpgradeableLoaderState::ProgramData {
+            slot: 0,
+            upgrade_authority_address: upgrade_authority,
+        })
+        .unwrap();
+        data.resize(UpgradeableLoaderState::size_of_programdata(16), 0);
+
+        let mut account = TestAccount::new(&solana_sdk_ids::bpf_loader_upgradeable::id(), data);
+        account.key = get_program_data_address(program_id);
+        account
+    }
+
+    fn initialize_with(
+        program_id: &Pubkey,
+        authority: &mut TestAccount,
+        program_data: &mut TestAccount,
+    ) -> ProgramResult {
+        let mut oracle_state = TestAccount::new(&system_program::id(), vec![]);
+        let mut system = TestAccount::new(&Pubkey::default(), vec![]);
+        system.key = system_program::id();
+        process_instruction(
+            program_id,
+            &[
+                authority.info(true),
+                oracle_state.info(false),
+                system.info(false),
+                program_data.info(false),
+            ],
+            &borsh::to_vec(&OracleInstruction::Initialize).unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_initialize_requires_upgrade_authority() {
+        let program_id = Pubkey::new_unique();
+        let mut authority = TestAccount::new(&system_program::id(), vec![]);
+        let mut front_runner = TestAccount::new(&system_program::id(), vec![]);
+
+        let mut deployed = program_data(&program_id, Some(authority.key));
+        assert_eq!(
+            initialize_with(&program_id, &mut front_runner, &mut deployed),
+            Err(OracleError::NotUpgradeAuthority.into())
+        );
+
+        // An immutable program has no authority left to initialize it
+        let mut immutable = program_data(&program_id, None);
+        assert_eq!(
+            initialize_with(&program_id, &mut authority, &mut immutable),
+            Err(OracleError::NotUpgradeAuthority.into())
+        );
+
+        // The upgrade authority gets past the gate to the oracle state check
+        assert_eq!(
+            initialize_with(&program_id, &mut authority, &mut deployed),
+            Err(OracleError::InvalidOracleState.into())
+        );
+    }
+
+    #[test]
+    fn test_initialize_rejects_foreign_program_data() {
+        let program_id = Pubkey::new_unique();
+        let mut authority = TestAccount::new(&system_program::id(), vec![]);
+
+        // ProgramData of another program
+        let mut other = program_data(&Pubkey::new_unique(), Some(authority.key));
+        assert_eq!(
+            initialize_with(&program_id, &mut authority, &mut other),
+            Err(OracleError::InvalidProgramData.into())
+        );
+
+        // Right address, but not owned by the upgradeable loader
+        let mut forged = program_data(&program_id, Some(authority.key));
+        forged.owner = system_program::id();
+        assert_eq!(
+            initialize_with(&program_id, &mut authority, &mut forged),
+            Err(OracleError::InvalidProgramData.into())
+        );
+    }
+
+    fn price_feed(commodity: [u8; 32], timestamp: i64) -> PriceFeed {
+        PriceFeed {
+            commodity,
+            price: 15000,
+            confidence: 100,
+            timestamp,
+            decimals: 2,
+        }
+    }
+
+    fn get_price_with(program_id: &Pubkey, feed: &mut TestAccount, max_age: i64) -> ProgramResult {
+        process_instruction(
+            program_id,
+            &[feed.info(false)],
+            &borsh::to_vec(&OracleInstruction::GetPrice { max_age }).unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_get_price_rejects_invalid_feed_accounts() {
+        let program_id = Pubkey::new_unique();
+        let maize = [1u8; 32];
+        let data = borsh::to_vec(&price_feed(maize, 1_000)).unwrap();
+
+        let mut feed = TestAccount::new(&program_id, data.clone());
+        feed.key = find_price_feed_address(&program_id, &maize).0;
+        assert_eq!(
+            get_price_with(&program_id, &mut feed, -1),
+            Err(OracleError::InvalidMaxAge.into())
+        );
+
+        // Same data in an account the oracle doesn't own
+        let mut foreign = TestAccount::new(&Pubkey::new_unique(), data.clone());
+        foreign.key = feed.key;
+        assert_eq!(
+            get_price_with(&program_id, &mut foreign, 60),
+            Err(ProgramError::IncorrectProgramId)
+        );
+
+        // Oracle account that isn't the PDA of the commodity it claims to price
+        let mut other = TestAccount::new(&program_id, data);
+        other.key = find_price_feed_address(&program_id, &[2u8; 32]).0;
+        assert_eq!(
+            get_price_with(&program_id, &mut other, 60),
+            Err(OracleError::PriceFeedMismatch.into())
+        );
+    }
+
+    #[test]
+    fn test_get_price_returns_only_fresh_prices() {
+        let feed = price_feed([1u8; 32], 1_000);
+        assert_eq!(
+            price_return_data(&feed, 1_061, 60),
+            Err(OracleError::StalePrice.into())
+        );
+
+        let data = price_return_data(&feed, 1_060, 60).unwrap();
+        let returned = PriceFeed::try_from_slice(&data).unwrap();
+        assert_eq!(
+            (returned.commodity, returned.price, returned.timestamp),
+            (feed.commodity, feed.price, feed.timestamp)
+        );
+    }
+
+    #[test]
+    fn test_update_price_rejects_feed_of_another_commodity() {
+        let program_id = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+        let oracle_state = OracleState {
+            authority,
+            is_initialized: true,
+        };
+
+        let mut signer = TestAccount::new(&system_program::id(), vec![]);
+        signer.key = authority;
+        let mut state = TestAccount::new(&program_id, borsh::to_vec(&oracle_state).unwrap());
+        state.key = find_oracle_state_address(&program_id).0;
+        let mut feed = TestAccount::new(&system_program::id(), vec![]);
+        feed.key = find_price_feed_address(&program_id, &[2u8; 32]).0;
+        let mut system = TestAccount::new(&Pubkey::default(), vec![]);
+        system.key = system_program::id();
+
+        let instruction = OracleInstruction::UpdatePrice {
+            commodity: [1u8; 32],
+            price: 15000,
+            confidence: 100,
+            decimals: 2,
+        };
+        assert_eq!(
+            process_instruction(
+                &program_id,
+                &[signer.info(true), state.info(false), feed.info(false), system.info(false)],
+                &borsh::to_vec(&instruction).unwrap(),
+            ),
+            Err(OracleError::PriceFeedMismatch.into())
+        );
+    }
+}
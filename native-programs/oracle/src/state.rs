@@ -0,0 +1,126 @@
+//! Oracle account state and PDA derivation
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::pubkey::Pubkey;
+
+/// Seed of the oracle state PDA
+pub const ORACLE_STATE_SEED: &[u8] = b"oracle_state";
+
+/// Seed prefix of the price feed PDAs, followed by the commodity
+pub const PRICE_SEED: &[u8] = b"price";
+
+/// Oracle state account data
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
+pub struct OracleState {
+    pub authority: Pubkey,
+    pub is_initialized: bool,
+}
+
+impl OracleState {
+    pub const LEN: usize = 32 + 1;
+}
+
+/// Price feed account data
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
+pub struct PriceFeed {
+    pub commodity: [u8; 32], // commodity name (padded)
+    pub price: u64,          // price in smallest units (e.g., cents)
+    pub confidence: u64,     // confidence interval
+    pub timestamp: i64,      // unix timestamp
+    pub decimals: u8,        // price decimals
+}
+
+impl PriceFeed {
+    pub const LEN: usize = 32 + 8 + 8 + 8 + 1;
+
+    /// Whether the price is older than `max_age` seconds at `now`
+    pub fn is_stale(&self, now: i64, max_age: i64) -> bool {
+        now.saturating_sub(self.timestamp) > max_age
+    }
+}
+
+/// Address and bump of the oracle state PDA
+pub fn find_oracle_state_address(program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[ORACLE_STATE_SEED], program_id)
+}
+
+/// Address and bump of the price feed PDA for `commodity`
+pub fn find_price_feed_address(program_id: &Pubkey, commodity: &[u8; 32]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[PRICE_SEED, commodity], program_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_oracle_state_serialization() {
+        let oracle_state = OracleState {
+            authority: Pubkey::new_unique(),
+            is_initialized: true,
+        };
+
+        let mut buffer = vec![0u8; OracleState::LEN];
+        oracle_state.serialize(&mut buffer.as_mut_slice()).unwrap();
+
+        let deserialized = OracleState::try_from_slice(&buffer).unwrap();
+        assert_eq!(oracle_state.authority, deserialized.authority);
+        assert_eq!(oracle_state.is_initialized, deserialized.is_initialized);
+    }
+
+    #[test]
+    fn test_price_feed_serialization() {
+        let mut commodity = [0u8; 32];
+        commodity[..5].copy_from_slice(b"MAIZE");
+
+        let price_feed = PriceFeed {
+            commodity,
+            price: 15000,
+            confidence: 100,
+            timestamp: 1696896000,
+            decimals: 2,
+        };
+
+        let mut buffer = vec![0u8; PriceFeed::LEN];
+        price_feed.serialize(&mut buffer.as_mut_slice()).unwrap();
+
+        let deserialized = PriceFeed::try_from_slice(&buffer).unwrap();
+        assert_eq!(price_feed.commodity, deserialized.commodity);
+        assert_eq!(price_feed.price, deserialized.price);
+        assert_eq!(price_feed.confidence, deserialized.confidence);
+        assert_eq!(price_feed.timestamp, deserialized.timestamp);
+        assert_eq!(price_feed.decimals, deserialized.decimals);
+    }
+
+    #[test]
+    fn test_price_feed_staleness() {
+        let price_feed = PriceFeed {
+            commodity: [0u8; 32],
+            price: 15000,
+            confidence: 100,
+            timestamp: 1696896000,
+            decimals: 2,
+        };
+
+        assert!(!price_feed.is_stale(1696896000 + 3600, 3600));
+        assert!(price_feed.is_stale(1696896000 + 3601, 3600));
+        assert!(!price_feed.is_stale(i64::MIN, 0));
+    }
+
+    #[test]
+    fn test_price_feed_address_per_commodity() {
+        let program_id = Pubkey::new_unique();
+        let mut maize = [0u8; 32];
+        maize[..5].copy_from_slice(b"MAIZE");
+        let mut wheat = [0u8; 32];
+        wheat[..5].copy_from_slice(b"WHEAT");
+
+        let (maize_feed, bump) = find_price_feed_address(&program_id, &maize);
+        assert_ne!(maize_feed, find_price_feed_address(&program_id, &wheat).0);
+        assert_ne!(maize_feed, find_oracle_state_address(&program_id).0);
+        assert_eq!(
+            Pubkey::create_program_address(&[PRICE_SEED, &maize, &[bump]], &program_id).unwrap(),
+            maize_feed
+        );
+    }
+}
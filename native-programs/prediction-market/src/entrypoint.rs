@@ -0,0 +1,22 @@
+//! Program entrypoint
+
+#![cfg(not(feature = "no-entrypoint"))]
+
+use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult, pubkey::Pubkey};
+
+use crate::{error::MarketError, processor};
+
+solana_program::entrypoint!(process_instruction);
+
+fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    if let Err(error) = processor::process_instruction(program_id, accounts, instruction_data) {
+        #[allow(deprecated)]
+        solana_program::program_error::PrintProgramError::print::<MarketError>(&error);
+        return Err(error);
+    }
+    Ok(())
+}
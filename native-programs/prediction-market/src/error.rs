@@ -0,0 +1,109 @@
+//! Error types
+
+use num_derive::FromPrimitive;
+use num_traits::FromPrimitive;
+#[allow(deprecated)]
+use solana_program::{
+    decode_error::DecodeError,
+    instruction::InstructionError,
+    msg,
+    program_error::{PrintProgramError, ProgramError},
+};
+use thiserror::Error;
+
+/// Errors that may be returned by the prediction market program. Codes start
+/// at 100 so they can't be mistaken for oracle errors raised through CPI.
+#[derive(Clone, Copy, Debug, Eq, Error, FromPrimitive, PartialEq)]
+pub enum MarketError {
+    /// 100 - Market has passed its expiry
+    #[error("Market has expired")]
+    MarketExpired = 100,
+    /// 101 - Market has not reached its expiry
+    #[error("Market has not expired yet")]
+    MarketNotExpired,
+    /// 102 - Market was already resolved
+    #[error("Market is already resolved")]
+    AlreadyResolved,
+    /// 103 - Market has not been resolved
+    #[error("Market is not resolved yet")]
+    MarketNotResolved,
+    /// 104 - Oracle program or price feed differs from the market's
+    #[error("Oracle program or price feed does not match the market")]
+    WrongPriceFeed,
+    /// 105 - Position winnings were already paid out
+    #[error("Winnings already claimed")]
+    AlreadyClaimed,
+    /// 106 - Position belongs to another user or market
+    #[error("Position does not belong to the signer")]
+    PositionOwnerMismatch,
+    /// 107 - Position holds no shares of the winning outcome
+    #[error("No winning shares to claim")]
+    NoWinningShares,
+    /// 108 - Token account is not the market's USDC vault
+    #[error("Vault does not match the market")]
+    WrongVault,
+}
+
+impl From<MarketError> for ProgramError {
+    fn from(e: MarketError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
+
+#[allow(deprecated)]
+impl<T> DecodeError<T> for MarketError {
+    fn type_of() -> &'static str {
+        "MarketError"
+    }
+}
+
+#[allow(deprecated)]
+impl PrintProgramError for MarketError {
+    fn print<E>(&self)
+    where
+        E: 'static + std::error::Error + DecodeError<E> + PrintProgramError + FromPrimitive,
+    {
+        msg!("Error: {}", self);
+    }
+}
+
+impl MarketError {
+    /// Decode a custom error code returned by the market
+    pub fn from_code(code: u32) -> Option<Self> {
+        Self::from_u32(code)
+    }
+
+    /// Decode the market error behind a program error, for clients
+    pub fn from_program_error(error: &ProgramError) -> Option<Self> {
+        match error {
+            ProgramError::Custom(code) => Self::from_code(*code),
+            _ => None,
+        }
+    }
+
+    /// Decode the market error of a failed transaction's instruction
+    pub fn from_instruction_error(error: &InstructionError) -> Option<Self> {
+        match error {
+            InstructionError::Custom(code) => Self::from_code(*code),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_codes_round_trip() {
+        let error: ProgramError = MarketError::AlreadyClaimed.into();
+        assert_eq!(error, ProgramError::Custom(105));
+        assert_eq!(MarketError::from_program_error(&error), Some(MarketError::AlreadyClaimed));
+        assert_eq!(
+            MarketError::from_instruction_error(&InstructionError::Custom(100)),
+            Some(MarketError::MarketExpired)
+        );
+        // Oracle errors raised through CPI don't decode as market errors
+        assert_eq!(MarketError::from_code(5), None);
+    }
+}
@@ -0,0 +1,195 @@
+//! Instruction types and builders
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    sysvar,
+};
+
+use crate::state::find_vault_authority_address;
+
+/// Instructions
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
+pub enum MarketInstruction {
+    /// Create a new prediction market
+    /// Accounts:
+    /// 0. `[writable, signer]` Creator account
+    /// 1. `[writable]` Market account, zeroed and owned by the program
+    /// 2. `[]` Oracle program
+    /// 3. `[]` Price feed account
+    /// 4. `[]` USDC vault account, owned by the vault authority PDA `[b"vault", market]`
+    CreateMarket {
+        commodity: [u8; 32],
+        target_price: u64,
+        expiry: i64,
+        decimals: u8,
+    },
+
+    /// Buy shares (YES or NO)
+    /// Accounts:
+    /// 0. `[signer]` User account
+    /// 1. `[writable]` Market account
+    /// 2. `[writable]` User position account, owned by the program; zeroed for a new position
+    /// 3. `[writable]` User USDC token account
+    /// 4. `[writable]` Market USDC vault
+    /// 5. `[]` SPL Token program
+    BuyShares {
+        amount: u64,
+        is_yes: bool,
+    },
+
+    /// Resolve market using oracle
+    /// Accounts:
+    /// 0. `[signer]` Authority account
+    /// 1. `[writable]` Market account
+    /// 2. `[]` Oracle program
+    /// 3. `[]` Price feed account
+    /// 4. `[]` Clock sysvar
+    ResolveMarket,
+
+    /// Claim winnings
+    /// Accounts:
+    /// 0. `[signer]` User account
+    /// 1. `[]` Market account
+    /// 2. `[writable]` User position account
+    /// 3. `[writable]` User USDC token account
+    /// 4. `[writable]` Market USDC vault
+    /// 5. `[]` Vault authority PDA `[b"vault", market]`
+    /// 6. `[]` SPL Token program
+    ClaimWinnings,
+}
+
+/// Creates a `CreateMarket` instruction. `market` must already be allocated
+/// with `PredictionMarket::LEN` bytes and owned by the program.
+#[allow(clippy::too_many_arguments)]
+pub fn create_market(
+    program_id: &Pubkey,
+    creator: &Pubkey,
+    market: &Pubkey,
+    oracle_program: &Pubkey,
+    price_feed: &Pubkey,
+    vault: &Pubkey,
+    commodity: [u8; 32],
+    target_price: u64,
+    expiry: i64,
+    decimals: u8,
+) -> Instruction {
+    Instruction::new_with_borsh(
+        *program_id,
+        &MarketInstruction::CreateMarket {
+            commodity,
+            target_price,
+            expiry,
+            decimals,
+        },
+        vec![
+            AccountMeta::new(*creator, true),
+            AccountMeta::new(*market, false),
+            AccountMeta::new_readonly(*oracle_program, false),
+            AccountMeta::new_readonly(*price_feed, false),
+            AccountMeta::new_readonly(*vault, false),
+        ],
+    )
+}
+
+/// Creates a `BuyShares` instruction paying `amount` USDC from `user_token`
+#[allow(clippy::too_many_arguments)]
+pub fn buy_shares(
+    program_id: &Pubkey,
+    user: &Pubkey,
+    market: &Pubkey,
+    position: &Pubkey,
+    user_token: &Pubkey,
+    vault: &Pubkey,
+    amount: u64,
+    is_yes: bool,
+) -> Instruction {
+    Instruction::new_with_borsh(
+        *program_id,
+        &MarketInstruction::BuyShares { amount, is_yes },
+        vec![
+            AccountMeta::new_readonly(*user, true),
+            AccountMeta::new(*market, false),
+            AccountMeta::new(*position, false),
+            AccountMeta::new(*user_token, false),
+            AccountMeta::new(*vault, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ],
+    )
+}
+
+/// Creates a `ResolveMarket` instruction against the market's oracle feed
+pub fn resolve_market(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    market: &Pubkey,
+    oracle_program: &Pubkey,
+    price_feed: &Pubkey,
+) -> Instruction {
+    Instruction::new_with_borsh(
+        *program_id,
+        &MarketInstruction::ResolveMarket,
+        vec![
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new(*market, false),
+            AccountMeta::new_readonly(*oracle_program, false),
+            AccountMeta::new_readonly(*price_feed, false),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+        ],
+    )
+}
+
+/// Creates a `ClaimWinnings` instruction paying out to `user_token`
+pub fn claim_winnings(
+    program_id: &Pubkey,
+    user: &Pubkey,
+    market: &Pubkey,
+    position: &Pubkey,
+    user_token: &Pubkey,
+    vault: &Pubkey,
+) -> Instruction {
+    Instruction::new_with_borsh(
+        *program_id,
+        &MarketInstruction::ClaimWinnings,
+        vec![
+            AccountMeta::new_readonly(*user, true),
+            AccountMeta::new_readonly(*market, false),
+            AccountMeta::new(*position, false),
+            AccountMeta::new(*user_token, false),
+            AccountMeta::new(*vault, false),
+            AccountMeta::new_readonly(find_vault_authority_address(program_id, market).0, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ],
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_claim_winnings_instruction() {
+        let program_id = Pubkey::new_unique();
+        let user = Pubkey::new_unique();
+        let market = Pubkey::new_unique();
+
+        let instruction = claim_winnings(
+            &program_id,
+            &user,
+            &market,
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+        );
+        assert_eq!(instruction.accounts.len(), 7);
+        assert!(instruction.accounts[0].is_signer && !instruction.accounts[0].is_writable);
+        assert!(!instruction.accounts[1].is_writable);
+        assert_eq!(instruction.accounts[5].pubkey, find_vault_authority_address(&program_id, &market).0);
+        assert_eq!(instruction.accounts[6].pubkey, spl_token::id());
+        assert!(matches!(
+            MarketInstruction::try_from_slice(&instruction.data).unwrap(),
+            MarketInstruction::ClaimWinnings
+        ));
+    }
+}
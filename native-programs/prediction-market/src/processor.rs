@@ -0,0 +1,760 @@
+//! Program instruction processor
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    program::{invoke, invoke_signed},
+    program_error::ProgramError,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    clock::Clock,
+    sysvar::Sysvar,
+};
+use spl_token::state::Account as TokenAccount;
+
+use crate::{
+    error::MarketError,
+    instruction::MarketInstruction,
+    state::{find_vault_authority_address, PredictionMarket, UserPosition, VAULT_AUTHORITY_SEED},
+};
+
+/// Oldest oracle price (seconds) a market resolves against
+pub const MAX_PRICE_AGE: i64 = 3600;
+
+/// Processes a `MarketInstruction`
+pub fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let instruction = MarketInstruction::try_from_slice(instruction_data)
+        .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+    match instruction {
+        MarketInstruction::CreateMarket {
+            commodity,
+            target_price,
+            expiry,
+            decimals,
+        } => {
+            msg!("Instruction: CreateMarket");
+            create_market(program_id, accounts, commodity, target_price, expiry, decimals)
+        }
+        MarketInstruction::BuyShares { amount, is_yes } => {
+            msg!("Instruction: BuyShares");
+            buy_shares(program_id, accounts, amount, is_yes)
+        }
+        MarketInstruction::ResolveMarket => {
+            msg!("Instruction: ResolveMarket");
+            resolve_market(program_id, accounts)
+        }
+        MarketInstruction::ClaimWinnings => {
+            msg!("Instruction: ClaimWinnings");
+            claim_winnings(program_id, accounts)
+        }
+    }
+}
+
+fn create_market(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    commodity: [u8; 32],
+    target_price: u64,
+    expiry: i64,
+    decimals: u8,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let creator_info = next_account_info(account_info_iter)?;
+    let market_info = next_account_info(account_info_iter)?;
+    let oracle_program_info = next_account_info(account_info_iter)?;
+    let price_feed_info = next_account_info(account_info_iter)?;
+    let vault_info = next_account_info(account_info_iter)?;
+
+    // Verify creator is signer
+    if !creator_info.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Verify market account is owned by program
+    if market_info.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    // Only a freshly allocated market account can be initialized
+    if market_info.data_len() != PredictionMarket::LEN {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    if is_initialized(&market_info.data.borrow()) {
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+
+    // The vault must be a token account held by this market's vault authority PDA
+    if *vault_info.owner != spl_token::id() {
+        return Err(MarketError::WrongVault.into());
+    }
+    let vault = TokenAccount::unpack(&vault_info.data.borrow())?;
+    if vault.owner != find_vault_authority_address(program_id, market_info.key).0 {
+        return Err(MarketError::WrongVault.into());
+    }
+
+    // Create market state
+    let market = PredictionMarket {
+        commodity,
+        target_price,
+        expiry,
+        resolved: false,
+        outcome: false,
+        yes_pool: 0,
+        no_pool: 0,
+        oracle_program: *oracle_program_info.key,
+        price_feed: *price_feed_info.key,
+        vault: *vault_info.key,
+        decimals,
+    };
+
+    // Serialize to account
+    market.serialize(&mut &mut market_info.data.borrow_mut()[..])?;
+
+    let commodity_name = String::from_utf8_lossy(
+        &commodity[..commodity.iter().position(|&c| c == 0).unwrap_or(32)]
+    );
+    msg!("Market created for {}: target={}, expiry={}", 
+         commodity_name, target_price, expiry);
+
+    Ok(())
+}
+
+fn buy_shares(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    amount: u64,
+    is_yes: bool,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let user_info = next_account_info(account_info_iter)?;
+    let market_info = next_account_info(account_info_iter)?;
+    let position_info = next_account_info(account_info_iter)?;
+    let user_token_info = next_account_info(account_info_iter)?;
+    let vault_info = next_account_info(account_info_iter)?;
+    let token_program_info = next_account_info(account_info_iter)?;
+
+    // Verify user is signer
+    if !user_info.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Verify market account is owned by program
+    if market_info.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    // Load market
+    let mut market = PredictionMarket::try_from_slice(&market_info.data.borrow())?;
+
+    // Only the vault chosen at creation takes deposits
+    if *vault_info.key != market.vault {
+        return Err(MarketError::WrongVault.into());
+    }
+
+    // Load or create user position, which must be the user's in this market
+    if position_info.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    if position_info.data_len() != UserPosition::LEN {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    let mut position = if is_initialized(&position_info.data.borrow()) {
+        UserPosition::try_from_slice(&position_info.data.borrow())?
+    } else {
+        UserPosition {
+            market: *market_info.key,
+            user: *user_info.key,
+            yes_shares: 0,
+            no_shares: 0,
+            claimed: false,
+        }
+    };
+    if position.user != *user_info.key || position.market != *market_info.key {
+        return Err(MarketError::PositionOwnerMismatch.into());
+    }
+    
+    // Check market not expired
+    let clock = Clock::get()?;
+    if clock.unix_timestamp >= market.expiry {
+        return Err(MarketError::MarketExpired.into());
+    }
+
+    // Check market not resolved
+    if market.resolved {
+        return Err(MarketError::AlreadyResolved.into());
+    }
+
+    // Transfer USDC from user to vault
+    let transfer_instruction = spl_token::instruction::transfer(
+        token_program_info.key,
+        user_token_info.key,
+        vault_info.key,
+        user_info.key,
+        &[],
+        amount,
+    )?;
+
+    invoke(
+        &transfer_instruction,
+        &[
+            user_token_info.clone(),
+            vault_info.clone(),
+            user_info.clone(),
+            token_program_info.clone(),
+        ],
+    )?;
+
+    // Calculate shares using AMM formula: shares = amount * (other_pool + k) / (same_pool + k)
+    // For simplicity, using 1:1 for now (can be enhanced with proper AMM math)
+    let shares = amount;
+
+    // Update pools
+    if is_yes {
+        market.yes_pool += amount;
+    } else {
+        market.no_pool += amount;
+    }
+
+    // Update market
+    market.serialize(&mut &mut market_info.data.borrow_mut()[..])?;
+
+    // Update shares
+    if is_yes {
+        position.yes_shares += shares;
+    } else {
+        position.no_shares += shares;
+    }
+
+    // Save position
+    position.serialize(&mut &mut position_info.data.borrow_mut()[..])?;
+
+    msg!("User bought {} {} shares for {} USDC", 
+         shares, if is_yes { "YES" } else { "NO" }, amount);
+
+    Ok(())
+}
+
+fn resolve_market(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let _authority_info = next_account_info(account_info_iter)?;
+    let market_info = next_account_info(account_info_iter)?;
+    let oracle_program_info = next_account_info(account_info_iter)?;
+    let price_feed_info = next_account_info(account_info_iter)?;
+
+    // Verify market account is owned by program
+    if market_info.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    // Load market
+    let mut market = PredictionMarket::try_from_slice(&market_info.data.borrow())?;
+
+    // Check not already resolved
+    if market.resolved {
+        return Err(MarketError::AlreadyResolved.into());
+    }
+
+    // Check market expired
+    let clock = Clock::get()?;
+    if clock.unix_timestamp < market.expiry {
+        return Err(MarketError::MarketNotExpired.into());
+    }
+
+    // Only the oracle and feed chosen at creation can settle the market
+    if *oracle_program_info.key != market.oracle_program || *price_feed_info.key != market.price_feed {
+        return Err(MarketError::WrongPriceFeed.into());
+    }
+
+    // Read price from oracle via CPI
+    let price_feed = commodity_oracle::cpi::get_price(oracle_program_info, price_feed_info, MAX_PRICE_AGE)?;
+    if price_feed.commodity != market.commodity {
+        return Err(MarketError::WrongPriceFeed.into());
+    }
+    let actual_price = price_feed.price;
+
+    // Determine outcome
+    market.outcome = actual_price >= market.target_price;
+    market.resolved = true;
+
+    // Save market
+    market.serialize(&mut &mut market_info.data.borrow_mut()[..])?;
+
+    msg!("Market resolved: actual_price={}, target={}, outcome={}", 
+         actual_price, market.target_price, market.outcome);
+
+    Ok(())
+}
+
+fn claim_winnings(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let user_info = next_account_info(account_info_iter)?;
+    let market_info = next_account_info(account_info_iter)?;
+    let position_info = next_account_info(account_info_iter)?;
+    let user_token_info = next_account_info(account_info_iter)?;
+    let vault_info = next_account_info(account_info_iter)?;
+    let vault_authority_info = next_account_info(account_info_iter)?;
+    let token_program_info = next_account_info(account_info_iter)?;
+
+    // Verify user is signer
+    if !user_info.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Verify market account is owned by program
+    if market_info.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    // Load market
+    let market = PredictionMarket::try_from_slice(&market_info.data.borrow())?;
+
+    // Check market is resolved
+    if !market.resolved {
+        return Err(MarketError::MarketNotResolved.into());
+    }
+
+    // Load position
+    if position_info.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    let mut position = UserPosition::try_from_slice(&position_info.data.borrow())?;
+
+    // Check not already claimed
+    if position.claimed {
+        return Err(MarketError::AlreadyClaimed.into());
+    }
+
+    // Check the position is the user's, in this market
+    if position.user != *user_info.key || position.market != *market_info.key {
+        return Err(MarketError::PositionOwnerMismatch.into());
+    }
+
+    // Calculate winnings
+    let winning_shares = if market.outcome {
+        position.yes_shares
+    } else {
+        position.no_shares
+    };
+
+    if winning_shares == 0 {
+        return Err(MarketError::NoWinningShares.into());
+    }
+
+    // Calculate payout: (winning_shares / winning_pool) * total_pool
+    let total_pool = market.yes_pool + market.no_pool;
+    let winning_pool = if market.outcome { market.yes_pool } else { market.no_pool };
+    
+    let payout = if winning_pool > 0 {
+        (winning_shares as u128 * total_pool as u128 / winning_pool as u128) as u64
+    } else {
+        0
+    };
+
+    if payout == 0 {
+        return Err(ProgramError::InsufficientFunds);
+    }
+
+    // Pay out of the market's vault, signed by its vault authority PDA
+    if *vault_info.key != market.vault {
+        return Err(MarketError::WrongVault.into());
+    }
+    let (vault_authority_key, bump) = find_vault_authority_address(program_id, market_info.key);
+    if *vault_authority_info.key != vault_authority_key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    // Transfer winnings from vault to user
+    let transfer_instruction = spl_token::instruction::transfer(
+        token_program_info.key,
+        vault_info.key,
+        user_token_info.key,
+        vault_authority_info.key,
+        &[],
+        payout,
+    )?;
+
+    invoke_signed(
+        &transfer_instruction,
+        &[
+            vault_info.clone(),
+            user_token_info.clone(),
+            vault_authority_info.clone(),
+            token_program_info.clone(),
+        ],
+        &[&[VAULT_AUTHORITY_SEED, market_info.key.as_ref(), &[bump]]],
+    )?;
+
+    // Mark as claimed
+    position.claimed = true;
+    position.serialize(&mut &mut position_info.data.borrow_mut()[..])?;
+
+    msg!("User claimed {} USDC in winnings", payout);
+
+    Ok(())
+}
+
+/// Accounts are allocated zeroed; any written byte means they were initialized
+fn is_initialized(data: &[u8]) -> bool {
+    data.iter().any(|&byte| byte != 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Owned storage behind a fabricated `AccountInfo`
+    struct TestAccount {
+        key: Pubkey,
+        owner: Pubkey,
+        lamports: u64,
+        data: Vec<u8>,
+    }
+
+    impl TestAccount {
+        fn new(owner: &Pubkey, data: Vec<u8>) -> Self {
+            Self {
+                key: Pubkey::new_unique(),
+                owner: *owner,
+                lamports: 1_000_000,
+                data,
+            }
+        }
+
+        fn info(&mut self, is_signer: bool) -> AccountInfo<'_> {
+            AccountInfo::new(
+                &self.key,
+                is_signer,
+                true,
+                &mut self.lamports,
+                &mut self.data,
+                &self.owner,
+                false,
+                0,
+            )
+        }
+    }
+
+    fn market(vault: Pubkey, resolved: bool) -> PredictionMarket {
+        PredictionMarket {
+            commodity: [0u8; 32],
+            target_price: 15000,
+            expiry: 1696896000,
+            resolved,
+            outcome: true,
+            yes_pool: 1000,
+            no_pool: 1000,
+            oracle_program: Pubkey::new_unique(),
+            price_feed: Pubkey::new_unique(),
+            vault,
+            decimals: 2,
+        }
+    }
+
+    fn position(market: Pubkey, user: Pubkey) -> UserPosition {
+        UserPosition {
+            market,
+            user,
+            yes_shares: 500,
+            no_shares: 0,
+            claimed: false,
+        }
+    }
+
+    fn buy_data() -> Vec<u8> {
+        borsh::to_vec(&MarketInstruction::BuyShares { amount: 100, is_yes: true }).unwrap()
+    }
+
+    fn claim_data() -> Vec<u8> {
+        borsh::to_vec(&MarketInstruction::ClaimWinnings).unwrap()
+    }
+
+    #[test]
+    fn test_rejects_market_owned_by_another_program() {
+        let program_id = Pubkey::new_unique();
+        let other = Pubkey::new_unique();
+        let vault_key = Pubkey::new_unique();
+
+        let mut user = TestAccount::new(&other, vec![]);
+        let mut market_account = TestAccount::new(&other, borsh::to_vec(&market(vault_key, false)).unwrap());
+        let mut position_account = TestAccount::new(&program_id, vec![]);
+        let mut user_token = TestAccount::new(&spl_token::id(), vec![]);
+        let mut vault = TestAccount::new(&spl_token::id(), vec![]);
+        vault.key = vault_key;
+        let mut token_program = TestAccount::new(&other, vec![]);
+
+        let accounts = [
+            user.info(true),
+            market_account.info(false),
+            position_account.info(false),
+            user_token.info(false),
+            vault.info(false),
+            token_program.info(false),
+        ];
+        assert_eq!(
+            process_instruction(&program_id, &accounts, &buy_data()),
+            Err(ProgramError::IncorrectProgramId)
+        );
+
+        let mut oracle_program = TestAccount::new(&other, vec![]);
+        let mut price_feed = TestAccount::new(&other, vec![]);
+        let accounts = [
+            accounts[0].clone(),
+            accounts[1].clone(),
+            oracle_program.info(false),
+            price_feed.info(false),
+        ];
+        let resolve_data = borsh::to_vec(&MarketInstruction::ResolveMarket).unwrap();
+        assert_eq!(
+            process_instruction(&program_id, &accounts, &resolve_data),
+            Err(ProgramError::IncorrectProgramId)
+        );
+    }
+
+    #[test]
+    fn test_buy_shares_rejects_foreign_vault() {
+        let program_id = Pubkey::new_unique();
+        let other = Pubkey::new_unique();
+
+        let mut user = TestAccount::new(&other, vec![]);
+        let mut market_account =
+            TestAccount::new(&program_id, borsh::to_vec(&market(Pubkey::new_unique(), false)).unwrap());
+        let mut position_account = TestAccount::new(&program_id, vec![]);
+        let mut user_token = TestAccount::new(&spl_token::id(), vec![]);
+        let mut vault = TestAccount::new(&spl_token::id(), vec![]);
+        let mut token_program = TestAccount::new(&other, vec![]);
+
+        let accounts = [
+            user.info(true),
+            market_account.info(false),
+            position_account.info(false),
+            user_token.info(false),
+            vault.info(false),
+            token_program.info(false),
+        ];
+        assert_eq!(
+            process_instruction(&program_id, &accounts, &buy_data()),
+            Err(MarketError::WrongVault.into())
+        );
+    }
+
+    #[test]
+    fn test_claim_rejects_position_from_another_market() {
+        let program_id = Pubkey::new_unique();
+        let other = Pubkey::new_unique();
+        let vault_key = Pubkey::new_unique();
+
+        let mut user = TestAccount::new(&other, vec![]);
+        let mut market_account = TestAccount::new(&program_id, borsh::to_vec(&market(vault_key, true)).unwrap());
+        let mut position_account = TestAccount::new(
+            &program_id,
+            borsh::to_vec(&position(Pubkey::new_unique(), user.key)).unwrap(),
+        );
+        let mut user_token = TestAccount::new(&spl_token::id(), vec![]);
+        let mut vault = TestAccount::new(&spl_token::id(), vec![]);
+        vault.key = vault_key;
+        let vault_authority_key = find_vault_authority_address(&program_id, &market_account.key).0;
+        let mut vault_authority = TestAccount::new(&other, vec![]);
+        vault_authority.key = vault_authority_key;
+        let mut token_program = TestAccount::new(&other, vec![]);
+
+        let accounts = [
+            user.info(true),
+            market_account.info(false),
+            position_account.info(false),
+            user_token.info(false),
+            vault.info(false),
+            vault_authority.info(false),
+            token_program.info(false),
+        ];
+        assert_eq!(
+            process_instruction(&program_id, &accounts, &claim_data()),
+            Err(MarketError::PositionOwnerMismatch.into())
+        );
+    }
+
+    #[test]
+    fn test_buy_shares_rejects_position_from_another_market() {
+        let program_id = Pubkey::new_unique();
+        let other = Pubkey::new_unique();
+        let vault_key = Pubkey::new_unique();
+
+        // A position opened in market X can't take shares bought in market Y
+        let mut user = TestAccount::new(&other, vec![]);
+        let mut market_account = TestAccount::new(&program_id, borsh::to_vec(&market(vault_key, false)).unwrap());
+        let mut position_account = TestAccount::new(
+            &program_id,
+            borsh::to_vec(&position(Pubkey::new_unique(), user.key)).unwrap(),
+        );
+        let mut user_token = TestAccount::new(&spl_token::id(), vec![]);
+        let mut vault = TestAccount::new(&spl_token::id(), vec![]);
+        vault.key = vault_key;
+        let mut token_program = TestAccount::new(&other, vec![]);
+
+        let mut accounts = [
+            user.info(true),
+            market_account.info(false),
+            position_account.info(false),
+            user_token.info(false),
+            vault.info(false),
+            token_program.info(false),
+        ];
+        assert_eq!(
+            process_instruction(&program_id, &accounts, &buy_data()),
+            Err(MarketError::PositionOwnerMismatch.into())
+        );
+
+        // Nor can another user's position in this market, or one the program doesn't own
+        let mut others_position = TestAccount::new(
+            &program_id,
+            borsh::to_vec(&position(*accounts[1].key, Pubkey::new_unique())).unwrap(),
+        );
+        accounts[2] = others_position.info(false);
+        assert_eq!(
+            process_instruction(&program_id, &accounts, &buy_data()),
+            Err(MarketError::PositionOwnerMismatch.into())
+        );
+
+        let mut forged_position = TestAccount::new(
+            &other,
+            borsh::to_vec(&position(*accounts[1].key, *accounts[0].key)).unwrap(),
+        );
+        accounts[2] = forged_position.info(false);
+        assert_eq!(
+            process_instruction(&program_id, &accounts, &buy_data()),
+            Err(ProgramError::IncorrectProgramId)
+        );
+
+        let mut short_position = TestAccount::new(&program_id, vec![0u8; UserPosition::LEN - 1]);
+        accounts[2] = short_position.info(false);
+        assert_eq!(
+            process_instruction(&program_id, &accounts, &buy_data()),
+            Err(ProgramError::InvalidAccountData)
+        );
+    }
+
+    #[test]
+    fn test_claim_rejects_wrong_vault_or_vault_authority() {
+        let program_id = Pubkey::new_unique();
+        let other = Pubkey::new_unique();
+        let vault_key = Pubkey::new_unique();
+
+        let mut user = TestAccount::new(&other, vec![]);
+        let mut market_account = TestAccount::new(&program_id, borsh::to_vec(&market(vault_key, true)).unwrap());
+        let mut position_account = TestAccount::new(
+            &program_id,
+            borsh::to_vec(&position(market_account.key, user.key)).unwrap(),
+        );
+        let mut user_token = TestAccount::new(&spl_token::id(), vec![]);
+        let mut vault = TestAccount::new(&spl_token::id(), vec![]);
+        vault.key = vault_key;
+        let mut foreign_vault = TestAccount::new(&spl_token::id(), vec![]);
+        // Another market's vault authority can't sign for this one
+        let mut vault_authority = TestAccount::new(&other, vec![]);
+        vault_authority.key = find_vault_authority_address(&program_id, &Pubkey::new_unique()).0;
+        let mut token_program = TestAccount::new(&other, vec![]);
+
+        let mut accounts = [
+            user.info(true),
+            market_account.info(false),
+            position_account.info(false),
+            user_token.info(false),
+            foreign_vault.info(false),
+            vault_authority.info(false),
+            token_program.info(false),
+        ];
+        assert_eq!(
+            process_instruction(&program_id, &accounts, &claim_data()),
+            Err(MarketError::WrongVault.into())
+        );
+
+        accounts[4] = vault.info(false);
+        assert_eq!(
+            process_instruction(&program_id, &accounts, &claim_data()),
+            Err(ProgramError::InvalidSeeds)
+        );
+    }
+
+    fn vault_data(owner: &Pubkey) -> Vec<u8> {
+        let mut data = vec![0u8; TokenAccount::LEN];
+        TokenAccount {
+            mint: Pubkey::new_unique(),
+            owner: *owner,
+            state: spl_token::state::AccountState::Initialized,
+            ..TokenAccount::default()
+        }
+        .pack_into_slice(&mut data);
+        data
+    }
+
+    #[test]
+    fn test_create_market_only_initializes_fresh_accounts_with_market_vault() {
+        let program_id = Pubkey::new_unique();
+        let other = Pubkey::new_unique();
+        let create_data = borsh::to_vec(&MarketInstruction::CreateMarket {
+            commodity: [0u8; 32],
+            target_price: 15000,
+            expiry: 1696896000,
+            decimals: 2,
+        })
+        .unwrap();
+
+        let mut creator = TestAccount::new(&other, vec![]);
+        let mut market_account = TestAccount::new(&program_id, vec![0u8; PredictionMarket::LEN]);
+        let mut oracle_program = TestAccount::new(&other, vec![]);
+        let mut price_feed = TestAccount::new(&other, vec![]);
+        let vault_authority = find_vault_authority_address(&program_id, &market_account.key).0;
+        let mut vault = TestAccount::new(&spl_token::id(), vault_data(&vault_authority));
+        // Token accounts held by anyone else, or not owned by the token program, are refused
+        let mut user_held_vault = TestAccount::new(&spl_token::id(), vault_data(&creator.key));
+        let mut fake_vault = TestAccount::new(&other, vault_data(&vault_authority));
+
+        let mut accounts = [
+            creator.info(true),
+            market_account.info(false),
+            oracle_program.info(false),
+            price_feed.info(false),
+            user_held_vault.info(false),
+        ];
+        assert_eq!(
+            process_instruction(&program_id, &accounts, &create_data),
+            Err(MarketError::WrongVault.into())
+        );
+        accounts[4] = fake_vault.info(false);
+        assert_eq!(
+            process_instruction(&program_id, &accounts, &create_data),
+            Err(MarketError::WrongVault.into())
+        );
+
+        accounts[4] = vault.info(false);
+        process_instruction(&program_id, &accounts, &create_data).unwrap();
+        let created = PredictionMarket::try_from_slice(&accounts[1].data.borrow()).unwrap();
+        assert_eq!(created.vault, *accounts[4].key);
+
+        // A live market can't be re-initialized with another oracle
+        let mut attacker_oracle = TestAccount::new(&other, vec![]);
+        accounts[2] = attacker_oracle.info(false);
+        assert_eq!(
+            process_instruction(&program_id, &accounts, &create_data),
+            Err(ProgramError::AccountAlreadyInitialized)
+        );
+        let mut oversized = TestAccount::new(&program_id, vec![0u8; PredictionMarket::LEN + 1]);
+        accounts[1] = oversized.info(false);
+        assert_eq!(
+            process_instruction(&program_id, &accounts, &create_data),
+            Err(ProgramError::InvalidAccountData)
+        );
+    }
+}
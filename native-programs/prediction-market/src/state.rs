@@ -0,0 +1,91 @@
+//! Market account state and PDA derivation
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::pubkey::Pubkey;
+
+/// Seed prefix of the vault authority PDAs, followed by the market
+pub const VAULT_AUTHORITY_SEED: &[u8] = b"vault";
+
+/// Market state
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
+pub struct PredictionMarket {
+    pub commodity: [u8; 32],     // commodity name
+    pub target_price: u64,       // target price to predict
+    pub expiry: i64,             // expiry timestamp
+    pub resolved: bool,          // is market resolved
+    pub outcome: bool,           // true if price >= target, false otherwise
+    pub yes_pool: u64,           // total USDC in YES pool
+    pub no_pool: u64,            // total USDC in NO pool
+    pub oracle_program: Pubkey,  // oracle program for price feed
+    pub price_feed: Pubkey,      // price feed account
+    pub vault: Pubkey,           // USDC vault account
+    pub decimals: u8,            // price decimals
+}
+
+impl PredictionMarket {
+    pub const LEN: usize = 32 + 8 + 8 + 1 + 1 + 8 + 8 + 32 + 32 + 32 + 1;
+}
+
+/// User position
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
+pub struct UserPosition {
+    pub market: Pubkey,
+    pub user: Pubkey,
+    pub yes_shares: u64,
+    pub no_shares: u64,
+    pub claimed: bool,
+}
+
+impl UserPosition {
+    pub const LEN: usize = 32 + 32 + 8 + 8 + 1;
+}
+
+/// Address and bump of the PDA that owns `market`'s USDC vault
+pub fn find_vault_authority_address(program_id: &Pubkey, market: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[VAULT_AUTHORITY_SEED, market.as_ref()], program_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_market_serialization() {
+        let mut commodity = [0u8; 32];
+        commodity[..5].copy_from_slice(b"MAIZE");
+
+        let market = PredictionMarket {
+            commodity,
+            target_price: 15000,
+            expiry: 1696896000,
+            resolved: false,
+            outcome: false,
+            yes_pool: 1000,
+            no_pool: 1000,
+            oracle_program: Pubkey::new_unique(),
+            price_feed: Pubkey::new_unique(),
+            vault: Pubkey::new_unique(),
+            decimals: 2,
+        };
+
+        let mut buffer = vec![0u8; PredictionMarket::LEN];
+        market.serialize(&mut buffer.as_mut_slice()).unwrap();
+
+        let deserialized = PredictionMarket::try_from_slice(&buffer).unwrap();
+        assert_eq!(market.target_price, deserialized.target_price);
+        assert_eq!(market.yes_pool, deserialized.yes_pool);
+    }
+
+    #[test]
+    fn test_vault_authority_per_market() {
+        let program_id = Pubkey::new_unique();
+        let market = Pubkey::new_unique();
+
+        let (vault_authority, bump) = find_vault_authority_address(&program_id, &market);
+        assert_ne!(vault_authority, find_vault_authority_address(&program_id, &Pubkey::new_unique()).0);
+        assert_eq!(
+            Pubkey::create_program_address(&[VAULT_AUTHORITY_SEED, market.as_ref(), &[bump]], &program_id).unwrap(),
+            vault_authority
+        );
+    }
+}
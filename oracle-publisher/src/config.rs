@@ -0,0 +1,132 @@
+//! Publisher configuration, read from a JSON file
+
+use std::path::Path;
+
+use oracle::FeedKey;
+use serde::Deserialize;
+
+use crate::PublisherError;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PublisherConfig {
+    #[serde(default = "default_rpc_url")]
+    pub rpc_url: String,
+    pub keypair_path: String,
+    pub source: SourceConfig,
+    #[serde(default = "default_poll_interval")]
+    pub poll_interval_secs: u64,    // Seconds between source polls
+    #[serde(default)]
+    pub retry: RetryPolicy,
+    #[serde(default)]
+    pub reward_pool: bool,          // Pass the reward pool to earn publishing points
+    pub feeds: Vec<FeedSpec>,
+}
+
+/// Where the `/api/live-prices` JSON comes from
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SourceConfig {
+    Url(String),                    // Plain `http://` endpoint, e.g. the local Next.js server
+    File(String),                   // Saved response, re-read on every poll
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct RetryPolicy {
+    pub max_attempts: u32,
+    pub backoff_ms: u64,            // Grows linearly with each attempt
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            backoff_ms: 500,
+        }
+    }
+}
+
+/// One oracle feed and the source quote that prices it
+#[derive(Debug, Clone, Deserialize)]
+pub struct FeedSpec {
+    pub symbol: String,             // `commodity` of the source quote, e.g. "COFFEE"
+    pub commodity: String,
+    #[serde(default)]
+    pub location: String,
+    #[serde(default)]
+    pub grade: String,
+    pub decimals: u8,               // Must match the feed config
+    #[serde(default = "default_price_scale")]
+    pub price_scale: f64,           // Source units to feed units, e.g. USD/lb to USD/MT
+    #[serde(default)]
+    pub use_local_price: bool,      // Publish `localPrice` (e.g. KES) instead of the USD price
+    #[serde(default = "default_confidence")]
+    pub confidence: u8,
+    pub heartbeat_secs: i64,        // Push at least this often
+    pub deviation_bps: u64,         // Push early on a move this large, 0 disables
+    #[serde(default = "default_max_quote_age")]
+    pub max_quote_age_secs: i64,    // Ignore source quotes older than this
+}
+
+impl FeedSpec {
+    pub fn feed_key(&self) -> FeedKey {
+        FeedKey::new(&self.commodity, &self.location, &self.grade)
+    }
+
+    /// Source price in feed units with the feed's decimals, if positive and in range
+    pub fn to_price(&self, value: f64) -> Option<u64> {
+        let scaled = (value * self.price_scale * 10f64.powi(self.decimals as i32)).round();
+        (scaled >= 1.0 && scaled < u64::MAX as f64).then_some(scaled as u64)
+    }
+}
+
+impl PublisherConfig {
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, PublisherError> {
+        let config: Self = serde_json::from_str(&std::fs::read_to_string(path)?)?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    pub fn validate(&self) -> Result<(), PublisherError> {
+        if self.feeds.is_empty() {
+            return Err(PublisherError::Config("no feeds configured".into()));
+        }
+        if self.retry.max_attempts == 0 {
+            return Err(PublisherError::Config("retry.max_attempts must be positive".into()));
+        }
+        for feed in &self.feeds {
+            if feed.confidence > 100 {
+                return Err(PublisherError::Config(format!("{}: confidence above 100", feed.symbol)));
+            }
+            if feed.heartbeat_secs <= 0 || feed.max_quote_age_secs <= 0 {
+                return Err(PublisherError::Config(format!(
+                    "{}: heartbeat and max quote age must be positive",
+                    feed.symbol
+                )));
+            }
+            if !feed.price_scale.is_finite() || feed.price_scale <= 0.0 {
+                return Err(PublisherError::Config(format!("{}: price_scale must be positive", feed.symbol)));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn default_rpc_url() -> String {
+    "http://127.0.0.1:8899".into()
+}
+
+fn default_poll_interval() -> u64 {
+    30
+}
+
+fn default_price_scale() -> f64 {
+    1.0
+}
+
+fn default_confidence() -> u8 {
+    90
+}
+
+fn default_max_quote_age() -> i64 {
+    3600
+}
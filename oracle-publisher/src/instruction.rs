@@ -0,0 +1,32 @@
+//! Oracle instructions sent by the publisher
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use oracle::pda;
+use oracle::FeedKey;
+
+/// `update_price` for `feed_key`, signed by `publisher`
+pub fn update_price(
+    publisher: &Pubkey,
+    feed_key: &FeedKey,
+    price: u64,
+    confidence: u8,
+    reward_pool: bool,
+) -> Instruction {
+    let (price_account, _) = pda::find_price_account_address(feed_key);
+    let accounts = oracle::accounts::UpdatePrice {
+        price_account,
+        oracle_state: pda::find_oracle_state_address().0,
+        price_history: pda::find_history_address(&price_account).0,
+        feed_config: pda::find_feed_config_address(&price_account).0,
+        publisher_account: pda::find_publisher_address(publisher).0,
+        reward_pool: reward_pool.then(|| pda::find_reward_pool_address().0),
+        publisher: *publisher,
+    };
+    Instruction {
+        program_id: oracle::ID,
+        accounts: accounts.to_account_metas(None),
+        data: oracle::instruction::UpdatePrice { price, confidence }.data(),
+    }
+}
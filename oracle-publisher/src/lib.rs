@@ -0,0 +1,289 @@
+//! Off-chain publisher for the Afrifutures oracle.
+//!
+//! Each tick fetches quotes from a `PriceSource`, scales them to the
+//! configured feeds and sends `update_price` whenever a feed's heartbeat is
+//! due or its price moved past the feed's deviation threshold.
+
+pub mod config;
+pub mod instruction;
+pub mod schedule;
+pub mod source;
+pub mod submit;
+
+use anchor_lang::prelude::Pubkey;
+
+use config::{FeedSpec, RetryPolicy};
+use schedule::{PushReason, Scheduler};
+use source::PriceSource;
+use submit::Submitter;
+
+#[derive(Debug, thiserror::Error)]
+pub enum PublisherError {
+    #[error("invalid config: {0}")]
+    Config(String),
+    #[error("price source: {0}")]
+    Source(String),
+    #[error("submit failed: {0}")]
+    Submit(String),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+}
+
+/// A push attempted during a tick
+#[derive(Debug)]
+pub struct Push {
+    pub symbol: String,
+    pub price: u64,
+    pub reason: PushReason,
+    pub result: Result<String, PublisherError>, // Transaction signature
+}
+
+pub struct Publisher<S, T> {
+    publisher: Pubkey,
+    feeds: Vec<FeedSpec>,
+    retry: RetryPolicy,
+    reward_pool: bool,
+    source: S,
+    submitter: T,
+    scheduler: Scheduler,
+}
+
+impl<S: PriceSource, T: Submitter> Publisher<S, T> {
+    pub fn new(publisher: Pubkey, feeds: Vec<FeedSpec>, source: S, submitter: T) -> Self {
+        Self {
+            publisher,
+            feeds,
+            retry: RetryPolicy::default(),
+            reward_pool: false,
+            source,
+            submitter,
+            scheduler: Scheduler::default(),
+        }
+    }
+
+    pub fn with_retry(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    pub fn with_reward_pool(mut self, reward_pool: bool) -> Self {
+        self.reward_pool = reward_pool;
+        self
+    }
+
+    pub fn source_mut(&mut self) -> &mut S {
+        &mut self.source
+    }
+
+    pub fn submitter(&self) -> &T {
+        &self.submitter
+    }
+
+    /// Fetch quotes once and push every feed that is due at `now`.
+    /// Feeds without a fresh quote are skipped; failed pushes are retried next tick.
+    pub fn tick(&mut self, now: i64) -> Result<Vec<Push>, PublisherError> {
+        let quotes = self.source.fetch()?;
+        let mut pushes = Vec::new();
+        for feed in &self.feeds {
+            let Some(quote) = quotes.iter().find(|q| q.symbol == feed.symbol) else {
+                continue;
+            };
+            if now - quote.timestamp > feed.max_quote_age_secs {
+                continue;
+            }
+            let value = if feed.use_local_price { quote.local_price } else { Some(quote.price) };
+            let Some(price) = value.and_then(|value| feed.to_price(value)) else {
+                continue;
+            };
+            let Some(reason) = self.scheduler.due(feed, price, now) else {
+                continue;
+            };
+            
+            let ix = instruction::update_price(
+                &self.publisher,
+                &feed.feed_key(),
+                price,
+                feed.confidence,
+                self.reward_pool,
+            );
+            let result = submit::submit_with_retries(&mut self.submitter, &ix, &self.retry);
+            if result.is_ok() {
+                self.scheduler.record(feed, price, now);
+            }
+            pushes.push(Push {
+                symbol: feed.symbol.clone(),
+                price,
+                reason,
+                result,
+            });
+        }
+        Ok(pushes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::solana_program::instruction::Instruction;
+    use anchor_lang::Discriminator;
+    use source::{SourceQuote, StubSource};
+
+    /// Records instructions, failing the first `failures` submissions
+    #[derive(Default)]
+    struct RecordingSubmitter {
+        failures: u32,
+        attempts: u32,
+        sent: Vec<Instruction>,
+    }
+
+    impl Submitter for RecordingSubmitter {
+        fn submit(&mut self, instruction: &Instruction) -> Result<String, PublisherError> {
+            self.attempts += 1;
+            if self.attempts <= self.failures {
+                return Err(PublisherError::Submit("blockhash not found".into()));
+            }
+            self.sent.push(instruction.clone());
+            Ok(format!("sig{}", self.sent.len()))
+        }
+    }
+
+    fn coffee_feed() -> FeedSpec {
+        FeedSpec {
+            symbol: "COFFEE".into(),
+            commodity: "COFFEE".into(),
+            location: "KE:NAIROBI".into(),
+            grade: String::new(),
+            decimals: 2,
+            price_scale: 1.0,
+            use_local_price: false,
+            confidence: 90,
+            heartbeat_secs: 300,
+            deviation_bps: 100,
+            max_quote_age_secs: 3600,
+        }
+    }
+
+    fn quote(price: f64, timestamp: i64) -> SourceQuote {
+        SourceQuote {
+            symbol: "COFFEE".into(),
+            price,
+            local_price: Some(price * 130.0),
+            timestamp,
+            source: "stub".into(),
+        }
+    }
+
+    fn no_backoff() -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: 3,
+            backoff_ms: 0,
+        }
+    }
+
+    #[test]
+    fn test_parse_live_prices_shapes() {
+        let single = r#"{"success":true,"commodity":"COFFEE","price":2.47,"currency":"USD",
+            "timestamp":"2024-05-01T12:00:00.000Z","source":"KAMIS",
+            "localPrice":{"price":321.1,"currency":"KES"},"cached":false}"#;
+        let quotes = source::parse_live_prices(single).unwrap();
+        assert_eq!(quotes.len(), 1);
+        assert_eq!(quotes[0].symbol, "COFFEE");
+        assert_eq!(quotes[0].local_price, Some(321.1));
+        assert_eq!(quotes[0].timestamp, 1_714_564_800);
+        
+        let multi = r#"{"success":true,"data":[
+            {"commodity":"WHEAT","price":6.1,"timestamp":1714564800000,"source":"World Bank"},
+            {"commodity":"MAIZE","price":null,"timestamp":"2024-05-01T12:00:00Z"}]}"#;
+        let quotes = source::parse_live_prices(multi).unwrap();
+        assert_eq!(quotes.len(), 1);
+        assert_eq!((quotes[0].symbol.as_str(), quotes[0].timestamp), ("WHEAT", 1_714_564_800));
+        assert_eq!(quotes[0].local_price, None);
+        
+        assert!(source::parse_live_prices(r#"{"success":false,"message":"No price data available"}"#).is_err());
+        assert_eq!(source::parse_iso8601("2024-05-01T15:00:00+03:00"), Some(1_714_564_800));
+        assert_eq!(source::parse_iso8601("1970-01-01T00:00:00Z"), Some(0));
+        
+        // Malformed, including multi-byte characters where ASCII separators belong
+        assert_eq!(source::parse_iso8601("2024é5-01T12:00:00Z"), None);
+        assert_eq!(source::parse_iso8601("2024-05-01T12:00:00é"), None);
+        assert_eq!(source::parse_iso8601("2024-05-01 12:00"), None);
+        let malformed = r#"{"success":true,"commodity":"COFFEE","price":2.47,"timestamp":"2024é5-01T12:00:00Z"}"#;
+        assert!(source::parse_live_prices(malformed).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_heartbeat_and_deviation_pushes() {
+        let feed = coffee_feed();
+        let source = StubSource { quotes: vec![quote(2.47, 1_000)] };
+        let publisher = Pubkey::new_unique();
+        let mut daemon = Publisher::new(publisher, vec![feed.clone()], source, RecordingSubmitter::default())
+            .with_retry(no_backoff());
+        
+        let pushes = daemon.tick(1_000).unwrap();
+        assert_eq!(pushes.len(), 1);
+        assert_eq!((pushes[0].price, pushes[0].reason), (247, PushReason::Initial));
+        
+        // Unchanged price inside the heartbeat is not pushed
+        assert!(daemon.tick(1_100).unwrap().is_empty());
+        
+        // A 0.8% move stays under the 100 bps threshold, 1.2% triggers a push
+        daemon.source_mut().quotes = vec![quote(2.49, 1_150)];
+        assert!(daemon.tick(1_150).unwrap().is_empty());
+        daemon.source_mut().quotes = vec![quote(2.50, 1_200)];
+        let pushes = daemon.tick(1_200).unwrap();
+        assert_eq!(pushes[0].reason, PushReason::Deviation(121));
+        
+        assert_eq!(daemon.tick(1_500).unwrap()[0].reason, PushReason::Heartbeat);
+        
+        // Quotes older than `max_quote_age_secs` are never published
+        assert!(daemon.tick(1_200 + 3_601 + 300).unwrap().is_empty());
+        
+        let sent = &daemon.submitter().sent;
+        assert_eq!(sent.len(), 3);
+        let ix = &sent[0];
+        assert_eq!(ix.program_id, oracle::ID);
+        assert_eq!(&ix.data[..8], oracle::instruction::UpdatePrice::DISCRIMINATOR);
+        assert_eq!(&ix.data[8..], [247u64.to_le_bytes().as_ref(), &[90]].concat());
+        let (price_account, _) = oracle::pda::find_price_account_address(&feed.feed_key());
+        assert_eq!(ix.accounts[0].pubkey, price_account);
+        assert!(ix.accounts.iter().any(|meta| meta.pubkey == publisher && meta.is_signer));
+    }
+
+    #[test]
+    fn test_retries_and_local_price() {
+        let mut feed = coffee_feed();
+        feed.use_local_price = true;
+        let submitter = RecordingSubmitter {
+            failures: 2,
+            ..Default::default()
+        };
+        let mut daemon = Publisher::new(
+            Pubkey::new_unique(),
+            vec![feed],
+            StubSource { quotes: vec![quote(2.5, 1_000)] },
+            submitter,
+        )
+        .with_retry(no_backoff());
+        
+        let pushes = daemon.tick(1_000).unwrap();
+        assert_eq!(pushes[0].price, 32_500);
+        assert_eq!(pushes[0].result.as_deref().unwrap(), "sig1");
+        assert_eq!(daemon.submitter().attempts, 3);
+        
+        // Exhausted retries leave the feed due on the next tick
+        let mut daemon = Publisher::new(
+            Pubkey::new_unique(),
+            vec![coffee_feed()],
+            StubSource { quotes: vec![quote(2.5, 1_000)] },
+            RecordingSubmitter {
+                failures: 3,
+                ..Default::default()
+            },
+        )
+        .with_retry(no_backoff());
+        assert!(daemon.tick(1_000).unwrap()[0].result.is_err());
+        assert_eq!(daemon.tick(1_010).unwrap()[0].reason, PushReason::Initial);
+    }
+}
@@ -0,0 +1,55 @@
+//! `oracle-publisher <config.json> [--once]`
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use oracle_publisher::config::PublisherConfig;
+use oracle_publisher::source::LivePricesSource;
+use oracle_publisher::submit::RpcSubmitter;
+use oracle_publisher::{Publisher, PublisherError};
+use solana_sdk::signature::read_keypair_file;
+
+fn main() -> Result<(), PublisherError> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let config_path = args
+        .iter()
+        .find(|arg| !arg.starts_with("--"))
+        .ok_or_else(|| PublisherError::Config("usage: oracle-publisher <config.json> [--once]".into()))?;
+    let once = args.iter().any(|arg| arg == "--once");
+    
+    let config = PublisherConfig::load(config_path)?;
+    let keypair = read_keypair_file(&config.keypair_path)
+        .map_err(|err| PublisherError::Config(format!("{}: {}", config.keypair_path, err)))?;
+    let submitter = RpcSubmitter::new(&config.rpc_url, keypair);
+    let publisher_key = submitter.publisher();
+    println!("Publishing {} feeds as {} to {}", config.feeds.len(), publisher_key, config.rpc_url);
+    
+    let mut publisher = Publisher::new(
+        publisher_key,
+        config.feeds.clone(),
+        LivePricesSource::new(config.source.clone()),
+        submitter,
+    )
+    .with_retry(config.retry)
+    .with_reward_pool(config.reward_pool);
+    
+    loop {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs() as i64;
+        match publisher.tick(now) {
+            Ok(pushes) => {
+                for push in pushes {
+                    match push.result {
+                        Ok(signature) => {
+                            println!("{} {} ({:?}): {}", push.symbol, push.price, push.reason, signature)
+                        }
+                        Err(err) => eprintln!("{} {} ({:?}) failed: {}", push.symbol, push.price, push.reason, err),
+                    }
+                }
+            }
+            Err(err) => eprintln!("Tick failed: {}", err),
+        }
+        if once {
+            return Ok(());
+        }
+        std::thread::sleep(Duration::from_secs(config.poll_interval_secs));
+    }
+}
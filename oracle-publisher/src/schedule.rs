@@ -0,0 +1,43 @@
+//! Heartbeat and deviation scheduling of feed pushes
+
+use std::collections::HashMap;
+
+use crate::config::FeedSpec;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PushReason {
+    Initial,                        // Nothing pushed since the publisher started
+    Heartbeat,
+    Deviation(u64),                 // Move in bps since the last push
+}
+
+#[derive(Debug, Clone, Copy)]
+struct LastPush {
+    price: u64,
+    at: i64,
+}
+
+/// Last successful push per feed symbol
+#[derive(Debug, Default)]
+pub struct Scheduler {
+    last: HashMap<String, LastPush>,
+}
+
+impl Scheduler {
+    /// Why `price` should be pushed to `feed` at `now`, if it should
+    pub fn due(&self, feed: &FeedSpec, price: u64, now: i64) -> Option<PushReason> {
+        let Some(last) = self.last.get(&feed.symbol) else {
+            return Some(PushReason::Initial);
+        };
+        if now - last.at >= feed.heartbeat_secs {
+            return Some(PushReason::Heartbeat);
+        }
+
+        let move_bps = (price.abs_diff(last.price) as u128 * 10_000 / last.price.max(1) as u128) as u64;
+        (feed.deviation_bps > 0 && move_bps >= feed.deviation_bps).then_some(PushReason::Deviation(move_bps))
+    }
+
+    pub fn record(&mut self, feed: &FeedSpec, price: u64, now: i64) {
+        self.last.insert(feed.symbol.clone(), LastPush { price, at: now });
+    }
+}
@@ -0,0 +1,168 @@
+//! Source adapters producing quotes in the `/api/live-prices` JSON shape
+
+use std::io::{Read, Write};
+use std::net::TcpStream;
+use std::time::Duration;
+
+use serde_json::Value;
+
+use crate::config::SourceConfig;
+use crate::PublisherError;
+
+const HTTP_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A commodity price as reported by the source, before scaling to a feed
+#[derive(Debug, Clone, PartialEq)]
+pub struct SourceQuote {
+    pub symbol: String,
+    pub price: f64,
+    pub local_price: Option<f64>,
+    pub timestamp: i64,             // Unix seconds
+    pub source: String,             // Upstream provider, e.g. "KAMIS"
+}
+
+pub trait PriceSource {
+    fn fetch(&mut self) -> Result<Vec<SourceQuote>, PublisherError>;
+}
+
+/// Reads `/api/live-prices` responses from a local HTTP endpoint or a file
+pub struct LivePricesSource {
+    config: SourceConfig,
+}
+
+impl LivePricesSource {
+    pub fn new(config: SourceConfig) -> Self {
+        Self { config }
+    }
+}
+
+impl PriceSource for LivePricesSource {
+    fn fetch(&mut self) -> Result<Vec<SourceQuote>, PublisherError> {
+        let body = match &self.config {
+            SourceConfig::Url(url) => http_get(url)?,
+            SourceConfig::File(path) => std::fs::read_to_string(path)?,
+        };
+        parse_live_prices(&body)
+    }
+}
+
+/// Fixed quotes, for tests and dry runs
+#[derive(Debug, Clone, Default)]
+pub struct StubSource {
+    pub quotes: Vec<SourceQuote>,
+}
+
+impl PriceSource for StubSource {
+    fn fetch(&mut self) -> Result<Vec<SourceQuote>, PublisherError> {
+        Ok(self.quotes.clone())
+    }
+}
+
+/// Parse a `/api/live-prices` response: a single quote for `?symbol=`, or a
+/// `data` array for `?symbols=`. Entries without a usable price are skipped.
+pub fn parse_live_prices(body: &str) -> Result<Vec<SourceQuote>, PublisherError> {
+    let response: Value = serde_json::from_str(body)?;
+    if response.get("success").and_then(Value::as_bool) != Some(true) {
+        let message = response.get("message").and_then(Value::as_str).unwrap_or("unsuccessful response");
+        return Err(PublisherError::Source(message.into()));
+    }
+
+    let entries = match response.get("data").and_then(Value::as_array) {
+        Some(data) => data.iter().collect(),
+        None => vec![&response],
+    };
+    Ok(entries.into_iter().filter_map(parse_quote).collect())
+}
+
+fn parse_quote(entry: &Value) -> Option<SourceQuote> {
+    Some(SourceQuote {
+        symbol: entry.get("commodity")?.as_str()?.to_string(),
+        price: entry.get("price")?.as_f64()?,
+        local_price: entry.get("localPrice").and_then(|local| local.get("price")?.as_f64()),
+        timestamp: parse_timestamp(entry.get("timestamp")?)?,
+        source: entry.get("source").and_then(Value::as_str).unwrap_or_default().to_string(),
+    })
+}
+
+/// ISO 8601 strings (how `Date` serializes) or Unix seconds/milliseconds
+fn parse_timestamp(value: &Value) -> Option<i64> {
+    match value {
+        Value::String(s) => parse_iso8601(s),
+        Value::Number(n) => {
+            let ts = n.as_i64()?;
+            Some(if ts > 100_000_000_000 { ts / 1000 } else { ts })
+        }
+        _ => None,
+    }
+}
+
+/// `YYYY-MM-DDTHH:MM:SS[.fff][Z|±HH:MM]`, as Unix seconds
+pub fn parse_iso8601(s: &str) -> Option<i64> {
+    // Fields are located by byte offset, which is only safe on ASCII
+    if !s.is_ascii() {
+        return None;
+    }
+    let num = |range: std::ops::Range<usize>| s.get(range)?.parse::<i64>().ok();
+    if s.len() < 19 || &s[4..5] != "-" || &s[7..8] != "-" || &s[13..14] != ":" || &s[16..17] != ":" {
+        return None;
+    }
+    let (year, month, day) = (num(0..4)?, num(5..7)?, num(8..10)?);
+    let (hour, minute, second) = (num(11..13)?, num(14..16)?, num(17..19)?);
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) || hour > 23 || minute > 59 || second > 60 {
+        return None;
+    }
+
+    let rest = s[19..].trim_start_matches(|c: char| c == '.' || c.is_ascii_digit());
+    let offset = match rest {
+        "" | "Z" => 0,
+        _ => {
+            let sign = match &rest[..1] {
+                "+" => 1,
+                "-" => -1,
+                _ => return None,
+            };
+            let hours = rest.get(1..3)?.parse::<i64>().ok()?;
+            let minutes = rest.get(rest.len() - 2..)?.parse::<i64>().ok()?;
+            sign * (hours * 3600 + minutes * 60)
+        }
+    };
+    Some(days_from_civil(year, month, day) * 86_400 + hour * 3600 + minute * 60 + second - offset)
+}
+
+/// Days since 1970-01-01 of a proleptic Gregorian date
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Minimal HTTP/1.0 GET, enough for a local `http://` endpoint
+fn http_get(url: &str) -> Result<String, PublisherError> {
+    let rest = url
+        .strip_prefix("http://")
+        .ok_or_else(|| PublisherError::Config(format!("only http:// sources are supported: {}", url)))?;
+    let (host, path) = match rest.find('/') {
+        Some(i) => (&rest[..i], &rest[i..]),
+        None => (rest, "/"),
+    };
+    let address = if host.contains(':') { host.to_string() } else { format!("{}:80", host) };
+    
+    let mut stream = TcpStream::connect(&address)?;
+    stream.set_read_timeout(Some(HTTP_TIMEOUT))?;
+    write!(stream, "GET {} HTTP/1.0\r\nHost: {}\r\nAccept: application/json\r\n\r\n", path, host)?;
+    let mut response = String::new();
+    stream.read_to_string(&mut response)?;
+    
+    let (head, body) = response
+        .split_once("\r\n\r\n")
+        .ok_or_else(|| PublisherError::Source("malformed HTTP response".into()))?;
+    let status = head.lines().next().unwrap_or_default();
+    // Error responses still carry the JSON `message`, so only reject non-JSON failures
+    if !status.contains(" 200 ") && !body.trim_start().starts_with('{') {
+        return Err(PublisherError::Source(status.to_string()));
+    }
+    Ok(body.to_string())
+}
@@ -0,0 +1,84 @@
+//! Transaction submission with retries
+
+use std::time::Duration;
+
+use anchor_lang::solana_program::instruction::Instruction;
+
+use crate::config::RetryPolicy;
+use crate::PublisherError;
+
+/// Signs and sends instructions, returning the transaction signature.
+/// Implement it over `BanksClient` to run the publisher in `solana-program-test`.
+pub trait Submitter {
+    fn submit(&mut self, instruction: &Instruction) -> Result<String, PublisherError>;
+}
+
+/// Submit `instruction`, retrying failures with a linearly growing backoff
+pub fn submit_with_retries<T: Submitter>(
+    submitter: &mut T,
+    instruction: &Instruction,
+    policy: &RetryPolicy,
+) -> Result<String, PublisherError> {
+    let mut attempt = 1;
+    loop {
+        match submitter.submit(instruction) {
+            Ok(signature) => return Ok(signature),
+            Err(err) if attempt >= policy.max_attempts => return Err(err),
+            Err(_) => {
+                std::thread::sleep(Duration::from_millis(policy.backoff_ms * attempt as u64));
+                attempt += 1;
+            }
+        }
+    }
+}
+
+#[cfg(feature = "rpc")]
+pub use rpc::RpcSubmitter;
+
+#[cfg(feature = "rpc")]
+mod rpc {
+    use solana_client::rpc_client::RpcClient;
+    use solana_sdk::commitment_config::CommitmentConfig;
+    use solana_sdk::signature::{Keypair, Signer};
+    use solana_sdk::transaction::Transaction;
+
+    use super::*;
+
+    /// Sends single-instruction transactions paid and signed by the publisher key
+    pub struct RpcSubmitter {
+        client: RpcClient,
+        publisher: Keypair,
+    }
+
+    impl RpcSubmitter {
+        pub fn new(rpc_url: &str, publisher: Keypair) -> Self {
+            Self {
+                client: RpcClient::new_with_commitment(rpc_url.to_string(), CommitmentConfig::confirmed()),
+                publisher,
+            }
+        }
+
+        pub fn publisher(&self) -> anchor_lang::prelude::Pubkey {
+            self.publisher.pubkey()
+        }
+    }
+
+    impl Submitter for RpcSubmitter {
+        fn submit(&mut self, instruction: &Instruction) -> Result<String, PublisherError> {
+            let blockhash = self
+                .client
+                .get_latest_blockhash()
+                .map_err(|err| PublisherError::Submit(err.to_string()))?;
+            let transaction = Transaction::new_signed_with_payer(
+                &[instruction.clone()],
+                Some(&self.publisher.pubkey()),
+                &[&self.publisher],
+                blockhash,
+            );
+            self.client
+                .send_and_confirm_transaction(&transaction)
+                .map(|signature| signature.to_string())
+                .map_err(|err| PublisherError::Submit(err.to_string()))
+        }
+    }
+}
@@ -0,0 +1,288 @@
+//! Checked reads of Afrifutures oracle accounts for consumer programs.
+//!
+//! `Account<'info, oracle::PriceAccount>` only proves an account is owned by
+//! the oracle and has the right discriminator. `CheckedPriceAccount` also
+//! checks the layout version and that the account is the canonical PDA of its
+//! feed, and `read` applies the same halt, retirement, staleness and quorum
+//! rules as the oracle's own `get_price`.
+
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use oracle::{FeedConfig, FeedKey, FeedLifecycle, FeedStatus, OracleState, PriceAccount, PriceData};
+
+#[error_code(offset = 7000)]
+pub enum OracleReadError {
+    #[msg("Account is not owned by the oracle program")]
+    WrongOwner,
+    #[msg("Account is not an oracle account of the expected type")]
+    WrongAccountType,
+    #[msg("Price account layout version is not supported")]
+    UnsupportedVersion,
+    #[msg("Account is not at the oracle PDA for its seeds")]
+    InvalidAddress,
+    #[msg("Price account is not the expected feed")]
+    WrongFeed,
+    #[msg("Oracle feed is halted by the circuit breaker")]
+    FeedHalted,
+    #[msg("Oracle feed is retired")]
+    FeedRetired,
+    #[msg("Oracle price is stale")]
+    StalePrice,
+    #[msg("Oracle aggregate is below the publisher quorum")]
+    InsufficientPublishers,
+}
+
+/// Deserialize an oracle account after checking its owner and discriminator
+fn load_account<T: AccountDeserialize + Discriminator>(info: &AccountInfo) -> Result<T> {
+    require_keys_eq!(*info.owner, oracle::ID, OracleReadError::WrongOwner);
+    let data = info.try_borrow_data()?;
+    require!(data.starts_with(T::DISCRIMINATOR), OracleReadError::WrongAccountType);
+    T::try_deserialize_unchecked(&mut &data[..])
+}
+
+/// Fail unless `address` is the oracle PDA of `seeds` and the stored `bump`
+fn verify_address(address: &Pubkey, seeds: &[&[u8]], bump: u8) -> Result<()> {
+    let bump = [bump];
+    let mut seeds = seeds.to_vec();
+    seeds.push(&bump);
+    let expected = Pubkey::create_program_address(&seeds, &oracle::ID)
+        .map_err(|_| error!(OracleReadError::InvalidAddress))?;
+    require_keys_eq!(*address, expected, OracleReadError::InvalidAddress);
+    Ok(())
+}
+
+/// A price account verified to be a current-layout oracle feed at its PDA
+#[derive(Clone)]
+pub struct CheckedPriceAccount {
+    key: Pubkey,
+    account: PriceAccount,
+}
+
+impl CheckedPriceAccount {
+    pub fn load(info: &AccountInfo) -> Result<Self> {
+        let account: PriceAccount = load_account(info)?;
+        require!(account.version == PriceAccount::VERSION, OracleReadError::UnsupportedVersion);
+        verify_address(
+            info.key,
+            &[b"price", &account.commodity, &account.location, &account.grade],
+            account.bump,
+        )?;
+        Ok(Self { key: *info.key, account })
+    }
+
+    /// Load a price account, requiring it to be the feed for `feed_key`
+    pub fn load_feed(info: &AccountInfo, feed_key: &FeedKey) -> Result<Self> {
+        let checked = Self::load(info)?;
+        require!(checked.account.feed_key() == *feed_key, OracleReadError::WrongFeed);
+        Ok(checked)
+    }
+
+    pub fn key(&self) -> Pubkey {
+        self.key
+    }
+
+    pub fn account(&self) -> &PriceAccount {
+        &self.account
+    }
+
+    /// Whether the feed is deprecated and past its grace period at `now`
+    pub fn is_retired(&self, now: i64) -> bool {
+        self.account.lifecycle == FeedLifecycle::Deprecated && now >= self.account.retire_at
+    }
+
+    /// Fail if the price is older than `max_age` seconds at `now`
+    pub fn check_fresh(&self, max_age: i64, now: i64) -> Result<()> {
+        require!(now - self.account.timestamp <= max_age, OracleReadError::StalePrice);
+        Ok(())
+    }
+
+    /// Price snapshot, failing unless the feed is active, not retired, within the
+    /// feed's `max_staleness` and backed by the oracle's publisher quorum
+    pub fn read(&self, feed_config: &CheckedFeedConfig, oracle_state: &OracleState, now: i64) -> Result<PriceData> {
+        let feed_config = feed_config.account();
+        require_keys_eq!(feed_config.price_account, self.key, OracleReadError::WrongFeed);
+        require!(self.account.status == FeedStatus::Active, OracleReadError::FeedHalted);
+        require!(!self.is_retired(now), OracleReadError::FeedRetired);
+        self.check_fresh(feed_config.max_staleness, now)?;
+        require!(
+            self.account.has_quorum(oracle_state.min_publishers),
+            OracleReadError::InsufficientPublishers
+        );
+        Ok(PriceData::new(self.key, &self.account, feed_config))
+    }
+}
+
+/// A feed config verified to be at the oracle PDA of its price account
+#[derive(Clone)]
+pub struct CheckedFeedConfig {
+    account: FeedConfig,
+}
+
+impl CheckedFeedConfig {
+    pub fn load(info: &AccountInfo, price_account: &Pubkey) -> Result<Self> {
+        let account: FeedConfig = load_account(info)?;
+        require_keys_eq!(account.price_account, *price_account, OracleReadError::WrongFeed);
+        verify_address(info.key, &[b"feed_config", price_account.as_ref()], account.bump)?;
+        Ok(Self { account })
+    }
+
+    pub fn account(&self) -> &FeedConfig {
+        &self.account
+    }
+}
+
+/// Load the oracle's global state, verified to be at its PDA
+pub fn load_oracle_state(info: &AccountInfo) -> Result<OracleState> {
+    let account: OracleState = load_account(info)?;
+    verify_address(info.key, &[b"oracle_state"], account.bump)?;
+    Ok(account)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use oracle::{Aggregate, PublisherQuote, MAX_PUBLISHERS};
+
+    fn price_account(feed_key: &FeedKey, bump: u8) -> PriceAccount {
+        PriceAccount {
+            version: PriceAccount::VERSION,
+            commodity: feed_key.commodity,
+            location: feed_key.location,
+            grade: feed_key.grade,
+            price: 24_700,
+            confidence: 95,
+            timestamp: 1_000,
+            last_updater: Pubkey::default(),
+            update_count: 1,
+            bump,
+            num_publishers: 3,
+            quotes: [PublisherQuote::default(); MAX_PUBLISHERS],
+            status: FeedStatus::Active,
+            halted_at: 0,
+            pending: Aggregate::default(),
+            reference_price: 0,
+            reference_timestamp: 0,
+            lifecycle: FeedLifecycle::Active,
+            retire_at: 0,
+            open_markets: 0,
+            volatility_bps: 0,
+            volatility_returns: 0,
+            open_fixings: 0,
+            _reserved: [0; PriceAccount::RESERVED],
+        }
+    }
+
+    fn feed_config(price_account: Pubkey, bump: u8) -> FeedConfig {
+        FeedConfig {
+            heartbeat: 60,
+            max_staleness: 300,
+            bump,
+            ..FeedConfig::test(price_account, 2)
+        }
+    }
+
+    fn serialize<T: AccountSerialize>(account: &T) -> Vec<u8> {
+        let mut data = Vec::new();
+        account.try_serialize(&mut data).unwrap();
+        data
+    }
+
+    fn with_account_info<R>(key: &Pubkey, owner: &Pubkey, data: &mut [u8], f: impl FnOnce(&AccountInfo) -> R) -> R {
+        let mut lamports = 0;
+        let info = AccountInfo::new(key, false, false, &mut lamports, data, owner, false, 0);
+        f(&info)
+    }
+
+    fn error_code(err: Error) -> u32 {
+        match err {
+            Error::AnchorError(e) => e.error_code_number,
+            Error::ProgramError(_) => panic!("expected an anchor error"),
+        }
+    }
+
+    #[test]
+    fn test_load_checks_owner_type_and_address() {
+        let feed_key = FeedKey::new("MAIZE", "KE:NAIROBI", "WHITE");
+        let (address, bump) = oracle::pda::find_price_account_address(&feed_key);
+        let mut data = serialize(&price_account(&feed_key, bump));
+        
+        let checked = with_account_info(&address, &oracle::ID, &mut data, |info| {
+            CheckedPriceAccount::load_feed(info, &feed_key)
+        })
+        .unwrap();
+        assert_eq!(checked.key(), address);
+        assert_eq!(checked.account().price, 24_700);
+        
+        let other = FeedKey::new("MAIZE", "KE:ELDORET", "WHITE");
+        let err = with_account_info(&address, &oracle::ID, &mut data, |info| {
+            CheckedPriceAccount::load_feed(info, &other)
+        });
+        assert_eq!(error_code(err.err().unwrap()), u32::from(OracleReadError::WrongFeed));
+        
+        let err = with_account_info(&address, &Pubkey::new_unique(), &mut data, CheckedPriceAccount::load);
+        assert_eq!(error_code(err.err().unwrap()), u32::from(OracleReadError::WrongOwner));
+        
+        let err = with_account_info(&Pubkey::new_unique(), &oracle::ID, &mut data, CheckedPriceAccount::load);
+        assert_eq!(error_code(err.err().unwrap()), u32::from(OracleReadError::InvalidAddress));
+        
+        // A feed config is owned by the oracle too, but is not a price account
+        let (config_address, config_bump) = oracle::pda::find_feed_config_address(&address);
+        let mut config_data = serialize(&feed_config(address, config_bump));
+        let err = with_account_info(&config_address, &oracle::ID, &mut config_data, CheckedPriceAccount::load);
+        assert_eq!(error_code(err.err().unwrap()), u32::from(OracleReadError::WrongAccountType));
+        
+        let mut old = price_account(&feed_key, bump);
+        old.version = 0;
+        let mut old_data = serialize(&old);
+        let err = with_account_info(&address, &oracle::ID, &mut old_data, CheckedPriceAccount::load);
+        assert_eq!(error_code(err.err().unwrap()), u32::from(OracleReadError::UnsupportedVersion));
+    }
+
+    #[test]
+    fn test_read_applies_feed_rules() {
+        let feed_key = FeedKey::new("COFFEE", "", "");
+        let (address, bump) = oracle::pda::find_price_account_address(&feed_key);
+        let (config_address, config_bump) = oracle::pda::find_feed_config_address(&address);
+        let mut config_data = serialize(&feed_config(address, config_bump));
+        let config = with_account_info(&config_address, &oracle::ID, &mut config_data, |info| {
+            CheckedFeedConfig::load(info, &address)
+        })
+        .unwrap();
+        let mut oracle_state = OracleState::try_deserialize_unchecked(
+            &mut &vec![0u8; 8 + OracleState::LEN][..],
+        )
+        .unwrap();
+        oracle_state.min_publishers = 3;
+        
+        let read = |account: PriceAccount, now: i64| {
+            let mut data = serialize(&account);
+            with_account_info(&address, &oracle::ID, &mut data, CheckedPriceAccount::load)
+                .unwrap()
+                .read(&config, &oracle_state, now)
+        };
+        
+        let data = read(price_account(&feed_key, bump), 1_300).unwrap();
+        assert_eq!((data.price, data.exponent, data.feed_id), (24_700, -2, address));
+        assert_eq!(
+            error_code(read(price_account(&feed_key, bump), 1_301).unwrap_err()),
+            u32::from(OracleReadError::StalePrice)
+        );
+        
+        let mut halted = price_account(&feed_key, bump);
+        halted.status = FeedStatus::Halted;
+        assert_eq!(error_code(read(halted, 1_000).unwrap_err()), u32::from(OracleReadError::FeedHalted));
+        
+        let mut retired = price_account(&feed_key, bump);
+        retired.lifecycle = FeedLifecycle::Deprecated;
+        retired.retire_at = 1_100;
+        assert!(read(retired.clone(), 1_099).is_ok());
+        assert_eq!(error_code(read(retired, 1_100).unwrap_err()), u32::from(OracleReadError::FeedRetired));
+        
+        let mut thin = price_account(&feed_key, bump);
+        thin.num_publishers = 2;
+        assert_eq!(
+            error_code(read(thin, 1_000).unwrap_err()),
+            u32::from(OracleReadError::InsufficientPublishers)
+        );
+    }
+}
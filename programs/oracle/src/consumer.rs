@@ -0,0 +1,47 @@
+//! Helpers for programs that read oracle feeds directly instead of through CPI
+
+use anchor_lang::prelude::*;
+
+use crate::{ErrorCode, FeedConfig, OracleState, PriceAccount, PriceData};
+
+/// Read one feed, applying the same freshness, quorum and halt checks as `get_price`
+pub fn read_price<'info>(
+    oracle_state: &OracleState,
+    price_account: &'info AccountInfo<'info>,
+    feed_config: &'info AccountInfo<'info>,
+) -> Result<PriceData> {
+    // `try_from` checks the accounts are owned by the oracle program
+    let price = Account::<PriceAccount>::try_from(price_account)?;
+    let config = Account::<FeedConfig>::try_from(feed_config)?;
+    require_keys_eq!(config.price_account, price.key(), ErrorCode::InvalidFeedConfig);
+    
+    price.check_readable(&config, oracle_state.min_publishers)?;
+    Ok(PriceData::new(price.key(), &price, &config))
+}
+
+/// Read several feeds in one instruction from (price_account, feed_config) pairs;
+/// fails as a whole if any feed is unreadable
+pub fn read_prices<'info>(
+    oracle_state: &'info AccountInfo<'info>,
+    feeds: &'info [AccountInfo<'info>],
+) -> Result<Vec<PriceData>> {
+    let oracle_state = Account::<OracleState>::try_from(oracle_state)?;
+    require_keys_eq!(
+        oracle_state.key(),
+        crate::pda::find_oracle_state_address().0,
+        ErrorCode::InvalidFeedConfig
+    );
+    read_feed_pairs(&oracle_state, feeds)
+}
+
+/// `read_prices` with an already validated `OracleState`
+pub fn read_feed_pairs<'info>(
+    oracle_state: &OracleState,
+    feeds: &'info [AccountInfo<'info>],
+) -> Result<Vec<PriceData>> {
+    let pairs = feeds.chunks_exact(2);
+    require!(pairs.remainder().is_empty(), ErrorCode::InvalidFeedConfig);
+    pairs
+        .map(|pair| read_price(oracle_state, &pair[0], &pair[1]))
+        .collect()
+}
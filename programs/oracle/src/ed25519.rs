@@ -0,0 +1,65 @@
+//! Verification of publisher signatures checked by the ed25519 sig-verify precompile
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions;
+
+use crate::ErrorCode;
+
+/// Native ed25519 signature verification program
+pub const ED25519_PROGRAM_ID: Pubkey = pubkey!("Ed25519SigVerify111111111111111111111111111");
+
+// Layout of the precompile's instruction data
+const HEADER_LEN: usize = 2; // num_signatures, padding
+const OFFSETS_LEN: usize = 14; // 7 x u16
+const PUBKEY_LEN: usize = 32;
+
+/// Instruction index the precompile uses for "data in this instruction"
+const CURRENT_INSTRUCTION: u16 = u16::MAX;
+
+/// Fail unless the instruction before the current one is an ed25519 sig-verify
+/// of `message` and return the public key that signed it
+pub fn verify_preceding_signature(instructions_sysvar: &AccountInfo, message: &[u8]) -> Result<Pubkey> {
+    let current = instructions::load_current_index_checked(instructions_sysvar)?;
+    require!(current > 0, ErrorCode::MissingSignatureVerification);
+
+    let ix = instructions::load_instruction_at_checked(current as usize - 1, instructions_sysvar)?;
+    require_keys_eq!(ix.program_id, ED25519_PROGRAM_ID, ErrorCode::MissingSignatureVerification);
+
+    let (signer, signed_message) = parse_signature_instruction(&ix.data)?;
+    require!(signed_message == message, ErrorCode::InvalidSignedReport);
+    Ok(signer)
+}
+
+/// Public key and message of a single-signature sig-verify instruction whose
+/// signature, key and message all live in its own data
+pub fn parse_signature_instruction(data: &[u8]) -> Result<(Pubkey, &[u8])> {
+    require!(
+        data.len() >= HEADER_LEN + OFFSETS_LEN && data[0] == 1,
+        ErrorCode::InvalidSignedReport
+    );
+
+    let offset = |i: usize| u16::from_le_bytes([data[HEADER_LEN + 2 * i], data[HEADER_LEN + 2 * i + 1]]);
+    let signature_ix = offset(1);
+    let pubkey_offset = offset(2) as usize;
+    let pubkey_ix = offset(3);
+    let message_offset = offset(4) as usize;
+    let message_len = offset(5) as usize;
+    let message_ix = offset(6);
+
+    // Offsets pointing into other instructions would let a signature over
+    // unrelated data vouch for this report
+    require!(
+        signature_ix == CURRENT_INSTRUCTION
+            && pubkey_ix == CURRENT_INSTRUCTION
+            && message_ix == CURRENT_INSTRUCTION,
+        ErrorCode::InvalidSignedReport
+    );
+
+    let pubkey = data
+        .get(pubkey_offset..pubkey_offset + PUBKEY_LEN)
+        .ok_or(ErrorCode::InvalidSignedReport)?;
+    let message = data
+        .get(message_offset..message_offset + message_len)
+        .ok_or(ErrorCode::InvalidSignedReport)?;
+    Ok((Pubkey::try_from(pubkey).unwrap(), message))
+}
@@ -0,0 +1,199 @@
+//! Decoders for third-party price accounts (Pyth receiver `PriceUpdateV2` and
+//! Switchboard On-Demand `PullFeedAccountData`), normalized to the price
+//! representation of `PriceAccount` so feeds can be cross-checked against them
+
+use anchor_lang::prelude::*;
+
+use crate::{ErrorCode, FeedConfig, PriceAccount, PriceSource, SourceRef};
+
+/// Pyth Solana receiver, owner of `PriceUpdateV2` accounts
+pub const PYTH_RECEIVER_PROGRAM_ID: Pubkey = pubkey!("rec5EKMGg6MxZYaMdyBfgwp4d5rB9T1VQH5pJv5LtFJ");
+
+/// Switchboard On-Demand, owner of `PullFeedAccountData` accounts
+pub const SWITCHBOARD_ON_DEMAND_PROGRAM_ID: Pubkey =
+    pubkey!("SBondMDrcV3K4kxZR1HNVT8osZxAHVHgYY75jWg6UyR");
+
+const PYTH_PRICE_UPDATE_DISCRIMINATOR: [u8; 8] = [34, 241, 35, 99, 157, 126, 244, 205];
+const SWITCHBOARD_PULL_FEED_DISCRIMINATOR: [u8; 8] = [196, 27, 108, 196, 10, 215, 219, 40];
+
+// Offsets into `PullFeedAccountData` after the discriminator
+const SWITCHBOARD_LAST_UPDATE_OFFSET: usize = 2208;
+const SWITCHBOARD_RESULT_OFFSET: usize = 2256;
+const SWITCHBOARD_RESULT_LEN: usize = 128;
+
+/// Switchboard results are fixed point with 18 decimals
+const SWITCHBOARD_DECIMALS: i32 = 18;
+
+/// A price in feed units: `price` at the feed's decimals and a spread-based
+/// confidence of 0-100, as in `PriceAccount`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SourcePrice {
+    pub price: u64,
+    pub confidence: u8,
+    pub timestamp: i64,
+}
+
+impl SourcePrice {
+    pub fn from_price_account(price_account: &PriceAccount) -> Self {
+        Self {
+            price: price_account.price,
+            confidence: price_account.confidence,
+            timestamp: price_account.timestamp,
+        }
+    }
+}
+
+/// Price message of a fully verified Pyth `PriceUpdateV2`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PythPrice {
+    pub feed_id: [u8; 32],
+    pub price: i64,
+    pub conf: u64,
+    pub exponent: i32,
+    pub publish_time: i64,
+}
+
+impl PythPrice {
+    /// Pyth's interval is `price ± conf`, so its spread is `2 * conf`
+    pub fn normalize(&self, decimals: u8) -> Result<SourcePrice> {
+        let price = scale(self.price as i128, self.exponent, decimals)?;
+        let spread = scale_spread(2 * self.conf as i128, self.exponent, decimals);
+        Ok(SourcePrice {
+            price,
+            confidence: spread_confidence(price, spread),
+            timestamp: self.publish_time,
+        })
+    }
+}
+
+/// Current result of a Switchboard pull feed
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SwitchboardPrice {
+    pub value: i128,
+    pub range: i128,                // Max minus min of the sampled oracle values
+    pub num_samples: u8,
+    pub last_update_timestamp: i64,
+}
+
+impl SwitchboardPrice {
+    pub fn normalize(&self, decimals: u8) -> Result<SourcePrice> {
+        require!(self.num_samples > 0, ErrorCode::InvalidSourceAccount);
+        let price = scale(self.value, -SWITCHBOARD_DECIMALS, decimals)?;
+        let spread = scale_spread(self.range, -SWITCHBOARD_DECIMALS, decimals);
+        Ok(SourcePrice {
+            price,
+            confidence: spread_confidence(price, spread),
+            timestamp: self.last_update_timestamp,
+        })
+    }
+}
+
+/// Decode a Pyth receiver `PriceUpdateV2`, rejecting partially verified updates
+pub fn decode_pyth_price_update(data: &[u8]) -> Result<PythPrice> {
+    require!(
+        data.get(..8) == Some(&PYTH_PRICE_UPDATE_DISCRIMINATOR[..]),
+        ErrorCode::InvalidSourceAccount
+    );
+    // write_authority, then the Borsh `VerificationLevel` (1 = Full)
+    require!(data.get(40) == Some(&1), ErrorCode::InvalidSourceAccount);
+    
+    let message = data.get(41..41 + 32 + 8 + 8 + 4 + 8).ok_or(ErrorCode::InvalidSourceAccount)?;
+    let mut feed_id = [0u8; 32];
+    feed_id.copy_from_slice(&message[..32]);
+    Ok(PythPrice {
+        feed_id,
+        price: i64::from_le_bytes(message[32..40].try_into().unwrap()),
+        conf: u64::from_le_bytes(message[40..48].try_into().unwrap()),
+        exponent: i32::from_le_bytes(message[48..52].try_into().unwrap()),
+        publish_time: i64::from_le_bytes(message[52..60].try_into().unwrap()),
+    })
+}
+
+/// Decode the current result of a Switchboard On-Demand `PullFeedAccountData`
+pub fn decode_switchboard_pull_feed(data: &[u8]) -> Result<SwitchboardPrice> {
+    require!(
+        data.get(..8) == Some(&SWITCHBOARD_PULL_FEED_DISCRIMINATOR[..]),
+        ErrorCode::InvalidSourceAccount
+    );
+    let body = &data[8..];
+    let timestamp = body
+        .get(SWITCHBOARD_LAST_UPDATE_OFFSET..SWITCHBOARD_LAST_UPDATE_OFFSET + 8)
+        .ok_or(ErrorCode::InvalidSourceAccount)?;
+    let result = body
+        .get(SWITCHBOARD_RESULT_OFFSET..SWITCHBOARD_RESULT_OFFSET + SWITCHBOARD_RESULT_LEN)
+        .ok_or(ErrorCode::InvalidSourceAccount)?;
+    
+    // value, std_dev, mean, range, min_value, max_value, num_samples
+    Ok(SwitchboardPrice {
+        value: i128::from_le_bytes(result[..16].try_into().unwrap()),
+        range: i128::from_le_bytes(result[48..64].try_into().unwrap()),
+        num_samples: result[96],
+        last_update_timestamp: i64::from_le_bytes(timestamp.try_into().unwrap()),
+    })
+}
+
+/// Normalized price of one of a feed's configured sources, failing if it is
+/// unreadable or older than the feed's `max_staleness`
+pub fn read_source(
+    source: &SourceRef,
+    account: &AccountInfo,
+    price_account: &PriceAccount,
+    feed_config: &FeedConfig,
+    min_publishers: u8,
+    now: i64,
+) -> Result<SourcePrice> {
+    let price = match source.kind {
+        PriceSource::Oracle => {
+            price_account.check_readable(feed_config, min_publishers)?;
+            return Ok(SourcePrice::from_price_account(price_account));
+        }
+        PriceSource::Pyth => {
+            require_keys_eq!(*account.owner, PYTH_RECEIVER_PROGRAM_ID, ErrorCode::InvalidSourceAccount);
+            decode_pyth_price_update(&account.try_borrow_data()?)?.normalize(feed_config.decimals)?
+        }
+        PriceSource::Switchboard => {
+            require_keys_eq!(
+                *account.owner,
+                SWITCHBOARD_ON_DEMAND_PROGRAM_ID,
+                ErrorCode::InvalidSourceAccount
+            );
+            decode_switchboard_pull_feed(&account.try_borrow_data()?)?.normalize(feed_config.decimals)?
+        }
+    };
+    require!(now - price.timestamp <= feed_config.max_staleness, ErrorCode::StalePrice);
+    Ok(price)
+}
+
+/// `value * 10^exponent` expressed with `decimals` decimals, truncated
+fn scale(value: i128, exponent: i32, decimals: u8) -> Result<u64> {
+    require!(value > 0, ErrorCode::InvalidSourceAccount);
+    let shift = exponent + decimals as i32;
+    let scaled = if shift >= 0 {
+        10i128
+            .checked_pow(shift as u32)
+            .and_then(|factor| value.checked_mul(factor))
+    } else {
+        Some(10i128.checked_pow(shift.unsigned_abs()).map_or(0, |factor| value / factor))
+    };
+    scaled
+        .and_then(|price| u64::try_from(price).ok())
+        .filter(|price| *price > 0)
+        .ok_or(error!(ErrorCode::InvalidSourceAccount))
+}
+
+/// Like `scale`, saturating instead of failing since it only feeds confidence
+fn scale_spread(value: i128, exponent: i32, decimals: u8) -> u64 {
+    let shift = exponent + decimals as i32;
+    let scaled = if shift >= 0 {
+        value.saturating_mul(10i128.saturating_pow(shift as u32))
+    } else {
+        10i128.checked_pow(shift.unsigned_abs()).map_or(0, |factor| value / factor)
+    };
+    scaled.clamp(0, u64::MAX as i128) as u64
+}
+
+/// Confidence drops one point per percent of spread, as for publisher quotes
+fn spread_confidence(price: u64, spread: u64) -> u8 {
+    let spread_pct = (spread as u128 * 100 / price as u128).min(100) as u8;
+    100 - spread_pct
+}
@@ -1,7 +1,59 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke_signed;
+use anchor_lang::system_program;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+use solana_sha256_hasher::hashv;
+
+pub mod consumer;
+pub mod ed25519;
+pub mod external;
+pub mod migration;
+pub mod pda;
 
 declare_id!("ora1exxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxx");
 
+/// Largest supported feed `decimals`
+pub const MAX_DECIMALS: u8 = 18;
+
+/// Maximum number of publishers in the set (one quote slot each per feed)
+pub const MAX_PUBLISHERS: usize = 8;
+
+/// Default window (seconds) in which a quote counts towards the aggregate
+pub const DEFAULT_PUBLISH_WINDOW: i64 = 300;
+
+/// Period over which `FeedConfig.max_hourly_change_bps` is measured
+pub const RATE_OF_CHANGE_PERIOD: i64 = 3600;
+
+/// Maximum number of feeds in one `update_prices` batch
+pub const MAX_BATCH_UPDATES: usize = 16;
+
+/// Maximum number of feeds returned by `get_prices` (return data is capped at 1 KiB)
+pub const MAX_PRICE_DATA_READS: usize = 12;
+
+/// Maximum number of members in the governance admin set
+pub const MAX_GOVERNANCE_MEMBERS: usize = 10;
+
+/// Delivery months held by a `ForwardCurve`
+pub const MAX_TENORS: usize = 12;
+
+/// Limits on the instruction a governance proposal can execute
+pub const MAX_PROPOSAL_ACCOUNTS: usize = 16;
+pub const MAX_PROPOSAL_DATA: usize = 512;
+
+/// Log returns between history observations in the rolling realized volatility
+pub const VOLATILITY_RETURNS: usize = 24;
+
+/// Volatility is annualized over this many seconds
+pub const SECONDS_PER_YEAR: i64 = 365 * 24 * 60 * 60;
+
+/// Observations kept per commodity in `PriceHistory`
+pub const HISTORY_CAPACITY: usize = 384;
+
+/// Observations are bucketed so the ring covers HISTORY_CAPACITY * HISTORY_INTERVAL (32h);
+/// fixing times fall on bucket boundaries
+pub const HISTORY_INTERVAL: i64 = 300;
+
 #[program]
 pub mod oracle {
     use super::*;
@@ -12,149 +64,4449 @@ pub mod oracle {
         oracle_state.authority = ctx.accounts.authority.key();
         oracle_state.total_commodities = 0;
         oracle_state.bump = ctx.bumps.oracle_state;
+        oracle_state.publishers = [Pubkey::default(); MAX_PUBLISHERS];
+        oracle_state.publisher_count = 0;
+        oracle_state.min_publishers = 1;
+        oracle_state.publish_window = DEFAULT_PUBLISH_WINDOW;
+        oracle_state.bond_mint = Pubkey::default();
+        oracle_state.min_bond = 0;
+        oracle_state.unbonding_period = 0;
+        oracle_state.slash_deviation_bps = 0;
+        oracle_state.pending_authority = Pubkey::default();
+        oracle_state.market_registry = Pubkey::default();
         
         msg!("Oracle initialized with authority: {}", oracle_state.authority);
         Ok(())
     }
 
-    /// Update commodity price (called by authorized oracle)
+    /// Add a price source to the publisher set (authority only)
+    pub fn add_publisher(ctx: Context<AddPublisher>, publisher: Pubkey) -> Result<()> {
+        let oracle_state = &mut ctx.accounts.oracle_state;
+        require!(publisher != Pubkey::default(), ErrorCode::InvalidPublisher);
+        require!(!oracle_state.is_publisher(&publisher), ErrorCode::PublisherExists);
+        require!(
+            (oracle_state.publisher_count as usize) < MAX_PUBLISHERS,
+            ErrorCode::PublisherSetFull
+        );
+        
+        let index = oracle_state.publisher_count as usize;
+        oracle_state.publishers[index] = publisher;
+        oracle_state.publisher_count += 1;
+        
+        // Keeps any bond left from an earlier membership
+        let publisher_account = &mut ctx.accounts.publisher_account;
+        publisher_account.publisher = publisher;
+        publisher_account.bump = ctx.bumps.publisher_account;
+        
+        emit!(PublisherAdded {
+            publisher,
+            publisher_count: oracle_state.publisher_count,
+        });
+        
+        msg!("Publisher added: {}", publisher);
+        Ok(())
+    }
+
+    /// Remove a price source from the publisher set (authority only)
+    pub fn remove_publisher(ctx: Context<UpdateConfig>, publisher: Pubkey) -> Result<()> {
+        let oracle_state = &mut ctx.accounts.oracle_state;
+        let count = oracle_state.publisher_count as usize;
+        let index = oracle_state
+            .active_publishers()
+            .iter()
+            .position(|p| *p == publisher)
+            .ok_or(ErrorCode::PublisherNotFound)?;
+        
+        oracle_state.publishers[index] = oracle_state.publishers[count - 1];
+        oracle_state.publishers[count - 1] = Pubkey::default();
+        oracle_state.publisher_count -= 1;
+        
+        emit!(PublisherRemoved {
+            publisher,
+            publisher_count: oracle_state.publisher_count,
+        });
+        
+        msg!("Publisher removed: {}", publisher);
+        Ok(())
+    }
+
+    /// Set the minimum number of publishers and the aggregation window (authority only)
+    pub fn set_quorum(
+        ctx: Context<UpdateConfig>,
+        min_publishers: u8,
+        publish_window: i64,
+    ) -> Result<()> {
+        require!(
+            min_publishers > 0 && min_publishers as usize <= MAX_PUBLISHERS,
+            ErrorCode::InvalidQuorum
+        );
+        require!(publish_window > 0, ErrorCode::InvalidQuorum);
+        
+        let oracle_state = &mut ctx.accounts.oracle_state;
+        oracle_state.min_publishers = min_publishers;
+        oracle_state.publish_window = publish_window;
+        
+        msg!("Quorum set: min_publishers={}, window={}s", min_publishers, publish_window);
+        Ok(())
+    }
+
+    /// Create the bond vault for `bond_mint` (AFF or USDC) and set bond requirements (authority only)
+    pub fn initialize_bonding(
+        ctx: Context<InitializeBonding>,
+        min_bond: u64,
+        unbonding_period: i64,
+        slash_deviation_bps: u16,
+    ) -> Result<()> {
+        let oracle_state = &mut ctx.accounts.oracle_state;
+        require!(
+            oracle_state.bond_mint == Pubkey::default(),
+            ErrorCode::BondingAlreadyInitialized
+        );
+        oracle_state.bond_mint = ctx.accounts.bond_mint.key();
+        oracle_state.set_bond_requirements(min_bond, unbonding_period, slash_deviation_bps)?;
+        
+        msg!(
+            "Bonding initialized: mint={}, min_bond={}, unbonding_period={}s",
+            oracle_state.bond_mint,
+            min_bond,
+            unbonding_period
+        );
+        Ok(())
+    }
+
+    /// Update the minimum bond, unbonding delay and slashing threshold (authority only)
+    pub fn set_bond_requirements(
+        ctx: Context<UpdateConfig>,
+        min_bond: u64,
+        unbonding_period: i64,
+        slash_deviation_bps: u16,
+    ) -> Result<()> {
+        let oracle_state = &mut ctx.accounts.oracle_state;
+        require!(
+            oracle_state.bond_mint != Pubkey::default(),
+            ErrorCode::BondingNotInitialized
+        );
+        oracle_state.set_bond_requirements(min_bond, unbonding_period, slash_deviation_bps)?;
+        
+        msg!("Bond requirements set: min_bond={}, unbonding_period={}s", min_bond, unbonding_period);
+        Ok(())
+    }
+
+    /// Lock tokens in the bond vault (publisher only)
+    pub fn bond(ctx: Context<Bond>, amount: u64) -> Result<()> {
+        require!(amount > 0, ErrorCode::InvalidBondAmount);
+        
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.publisher_token_account.to_account_info(),
+            to: ctx.accounts.bond_vault.to_account_info(),
+            authority: ctx.accounts.publisher.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        token::transfer(cpi_ctx, amount)?;
+        
+        let publisher_account = &mut ctx.accounts.publisher_account;
+        publisher_account.bonded = publisher_account
+            .bonded
+            .checked_add(amount)
+            .ok_or(ErrorCode::MathOverflow)?;
+        
+        emit!(PublisherBonded {
+            publisher: publisher_account.publisher,
+            amount,
+            bonded: publisher_account.bonded,
+        });
+        
+        msg!("Publisher bonded {} (total {})", amount, publisher_account.bonded);
+        Ok(())
+    }
+
+    /// Start the unbonding delay for part of the bond; unbonding tokens stay slashable
+    pub fn request_unbond(ctx: Context<RequestUnbond>, amount: u64) -> Result<()> {
+        let publisher_account = &mut ctx.accounts.publisher_account;
+        require!(
+            amount > 0 && amount <= publisher_account.bonded,
+            ErrorCode::InvalidBondAmount
+        );
+        
+        let clock = Clock::get()?;
+        publisher_account.bonded -= amount;
+        publisher_account.unbonding += amount;
+        // Each request restarts the delay for everything still unbonding
+        publisher_account.unbond_requested_at = clock.unix_timestamp;
+        
+        emit!(UnbondRequested {
+            publisher: publisher_account.publisher,
+            amount,
+            available_at: clock.unix_timestamp + ctx.accounts.oracle_state.unbonding_period,
+        });
+        
+        msg!("Unbond requested: {}", amount);
+        Ok(())
+    }
+
+    /// Withdraw unbonding tokens once the unbonding delay has passed
+    pub fn withdraw_unbonded(ctx: Context<WithdrawUnbonded>) -> Result<()> {
+        let oracle_state = &ctx.accounts.oracle_state;
+        let publisher_account = &mut ctx.accounts.publisher_account;
+        let amount = publisher_account.unbonding;
+        require!(amount > 0, ErrorCode::InvalidBondAmount);
+        
+        let clock = Clock::get()?;
+        require!(
+            clock.unix_timestamp >= publisher_account.unbond_requested_at + oracle_state.unbonding_period,
+            ErrorCode::UnbondingPeriodActive
+        );
+        publisher_account.unbonding = 0;
+        
+        let seeds = &[b"oracle_state".as_ref(), &[oracle_state.bump]];
+        let signer = &[&seeds[..]];
+        
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.bond_vault.to_account_info(),
+            to: ctx.accounts.publisher_token_account.to_account_info(),
+            authority: ctx.accounts.oracle_state.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        token::transfer(cpi_ctx, amount)?;
+        
+        msg!("Unbonded {} withdrawn", amount);
+        Ok(())
+    }
+
+    /// Slash a publisher for a signed report that deviates from the recorded
+    /// aggregate for its window by more than `slash_deviation_bps` (authority only)
+    ///
+    /// The report is proven by an ed25519 sig-verify instruction placed
+    /// immediately before this one.
+    pub fn slash_publisher(
+        ctx: Context<SlashPublisher>,
+        report: PriceReport,
+        amount: u64,
+    ) -> Result<()> {
+        let oracle_state = &ctx.accounts.oracle_state;
+        require!(oracle_state.slash_deviation_bps > 0, ErrorCode::BondingNotInitialized);
+        require_keys_eq!(
+            report.price_account,
+            ctx.accounts.price_account.key(),
+            ErrorCode::InvalidSignedReport
+        );
+        
+        let signer_key = ed25519::verify_preceding_signature(
+            &ctx.accounts.instructions_sysvar,
+            &report.message(),
+        )?;
+        let publisher_account = &mut ctx.accounts.publisher_account;
+        require_keys_eq!(signer_key, publisher_account.publisher, ErrorCode::InvalidSignedReport);
+        // A report can only be used as evidence once
+        require!(
+            report.timestamp > publisher_account.last_slashed_report,
+            ErrorCode::ReportReplayed
+        );
+        
+        let observation = *ctx
+            .accounts
+            .price_history
+            .load()?
+            .observation_in_bucket(report.timestamp)
+            .ok_or(ErrorCode::InsufficientHistory)?;
+        let deviation = deviation_bps(observation.price, report.price);
+        require!(
+            deviation > oracle_state.slash_deviation_bps as u64,
+            ErrorCode::ReportWithinTolerance
+        );
+        
+        let slashed = publisher_account.slash(amount);
+        require!(slashed > 0, ErrorCode::InvalidBondAmount);
+        publisher_account.last_slashed_report = report.timestamp;
+        
+        let seeds = &[b"oracle_state".as_ref(), &[oracle_state.bump]];
+        let signer = &[&seeds[..]];
+        
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.bond_vault.to_account_info(),
+            to: ctx.accounts.slash_destination.to_account_info(),
+            authority: ctx.accounts.oracle_state.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        token::transfer(cpi_ctx, slashed)?;
+        
+        emit!(PublisherSlashed {
+            publisher: publisher_account.publisher,
+            price_account: report.price_account,
+            reported_price: report.price,
+            aggregate_price: observation.price,
+            deviation_bps: deviation,
+            amount: slashed,
+        });
+        
+        msg!("Publisher slashed {} for a {} bps deviation", slashed, deviation);
+        Ok(())
+    }
+
+    /// Create the reward pool and its fee vault for `fee_mint` (authority only)
+    pub fn initialize_rewards(ctx: Context<InitializeRewards>) -> Result<()> {
+        let reward_pool = &mut ctx.accounts.reward_pool;
+        reward_pool.fee_mint = ctx.accounts.fee_mint.key();
+        reward_pool.total_points = 0;
+        reward_pool.total_deposited = 0;
+        reward_pool.bump = ctx.bumps.reward_pool;
+        reward_pool.rewards_per_point = 0;
+        reward_pool.undistributed = 0;
+        
+        msg!("Reward pool initialized: fee_mint={}", reward_pool.fee_mint);
+        Ok(())
+    }
+
+    /// Pay fees into the oracle fee vault (consumers, e.g. market resolution fees)
+    pub fn deposit_fees(ctx: Context<DepositFees>, amount: u64) -> Result<()> {
+        require!(amount > 0, ErrorCode::InvalidFeeAmount);
+        
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.depositor_token_account.to_account_info(),
+            to: ctx.accounts.fee_vault.to_account_info(),
+            authority: ctx.accounts.depositor.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        token::transfer(cpi_ctx, amount)?;
+        
+        let reward_pool = &mut ctx.accounts.reward_pool;
+        reward_pool.total_deposited = reward_pool.total_deposited.saturating_add(amount);
+        reward_pool.distribute(amount)?;
+        
+        emit!(FeesDeposited {
+            depositor: ctx.accounts.depositor.key(),
+            amount,
+        });
+        
+        msg!("Fees deposited: {}", amount);
+        Ok(())
+    }
+
+    /// Claim the fees earned by the publisher's unclaimed points: each deposit is
+    /// shared pro rata among the points unclaimed when it was made
+    pub fn claim_rewards(ctx: Context<ClaimRewards>) -> Result<()> {
+        let reward_pool = &mut ctx.accounts.reward_pool;
+        let publisher_account = &mut ctx.accounts.publisher_account;
+        let points = publisher_account.reward_points;
+        let amount = publisher_account.claim_rewards(reward_pool)?;
+        
+        let seeds = &[b"reward_pool".as_ref(), &[reward_pool.bump]];
+        let signer = &[&seeds[..]];
+        
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.fee_vault.to_account_info(),
+            to: ctx.accounts.publisher_token_account.to_account_info(),
+            authority: ctx.accounts.reward_pool.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        token::transfer(cpi_ctx, amount)?;
+        
+        emit!(RewardsClaimed {
+            publisher: ctx.accounts.publisher_account.publisher,
+            points,
+            amount,
+        });
+        
+        msg!("Rewards claimed: {} for {} points", amount, points);
+        Ok(())
+    }
+
+    /// Register a commodity feed for a market location and grade, with its
+    /// metadata, price account and history
+    pub fn create_feed(
+        ctx: Context<CreateFeed>,
+        feed_key: FeedKey,
+        params: FeedParams,
+    ) -> Result<()> {
+        params.validate()?;
+        
+        let clock = Clock::get()?;
+        let oracle_state = &mut ctx.accounts.oracle_state;
+        let index = oracle_state.total_commodities;
+        
+        let price_account = &mut ctx.accounts.price_account;
+        price_account.version = PriceAccount::VERSION;
+        price_account.commodity = feed_key.commodity;
+        price_account.location = feed_key.location;
+        price_account.grade = feed_key.grade;
+        price_account.bump = ctx.bumps.price_account;
+        
+        let feed_config = &mut ctx.accounts.feed_config;
+        feed_config.price_account = price_account.key();
+        feed_config.commodity = feed_key.commodity;
+        feed_config.index = index;
+        feed_config.created_at = clock.unix_timestamp;
+        feed_config.bump = ctx.bumps.feed_config;
+        feed_config.apply(&params);
+        
+        let feed_index = &mut ctx.accounts.feed_index;
+        feed_index.index = index;
+        feed_index.price_account = price_account.key();
+        feed_index.bump = ctx.bumps.feed_index;
+        
+        ctx.accounts.price_history.load_init()?.price_account = price_account.key();
+        
+        oracle_state.total_commodities += 1;
+        
+        emit!(FeedCreated {
+            commodity: feed_key.commodity,
+            location: feed_key.location,
+            grade: feed_key.grade,
+            price_account: price_account.key(),
+            index,
+        });
+        
+        msg!("Feed created: index={}, price_account={}", index, price_account.key());
+        Ok(())
+    }
+
+    /// Update feed metadata and staleness limits (authority only)
+    pub fn configure_feed(ctx: Context<ConfigureFeed>, params: FeedParams) -> Result<()> {
+        params.validate()?;
+        
+        let feed_config = &mut ctx.accounts.feed_config;
+        require!(
+            params.decimals == feed_config.decimals || ctx.accounts.price_account.update_count == 0,
+            ErrorCode::FeedHasPrices
+        );
+        feed_config.apply(&params);
+        
+        msg!(
+            "Feed configured: heartbeat={}s, max_staleness={}s",
+            params.heartbeat,
+            params.max_staleness
+        );
+        Ok(())
+    }
+
+    /// Set the primary and secondary sources `get_checked_price` compares for a
+    /// feed (authority only)
+    pub fn set_feed_sources(
+        ctx: Context<SetFeedSources>,
+        primary: SourceRef,
+        secondary: SourceRef,
+        max_deviation_bps: u16,
+    ) -> Result<()> {
+        let price_account_key = ctx.accounts.price_account.key();
+        // The oracle's own feed is referenced by its price account
+        for source in [&primary, &secondary] {
+            require!(
+                source.kind != PriceSource::Oracle || source.account == price_account_key,
+                ErrorCode::InvalidSourceConfig
+            );
+        }
+        require!(
+            primary.account != secondary.account && max_deviation_bps > 0,
+            ErrorCode::InvalidSourceConfig
+        );
+        
+        let feed_sources = &mut ctx.accounts.feed_sources;
+        feed_sources.price_account = price_account_key;
+        feed_sources.primary = primary;
+        feed_sources.secondary = secondary;
+        feed_sources.max_deviation_bps = max_deviation_bps;
+        feed_sources.bump = ctx.bumps.feed_sources;
+        
+        msg!(
+            "Feed sources set: primary={:?}, secondary={:?}, max_deviation={} bps",
+            primary.kind,
+            secondary.kind,
+            max_deviation_bps
+        );
+        Ok(())
+    }
+
+    /// Submit a publisher quote and re-aggregate the commodity price
     pub fn update_price(
         ctx: Context<UpdatePrice>,
-        commodity: [u8; 32],
         price: u64,
         confidence: u8,
     ) -> Result<()> {
+        require!(price > 0, ErrorCode::InvalidPrice);
         require!(confidence <= 100, ErrorCode::InvalidConfidence);
         
-        let price_account = &mut ctx.accounts.price_account;
-        let clock = Clock::get()?;
-        
-        price_account.commodity = commodity;
-        price_account.price = price;
-        price_account.confidence = confidence;
-        price_account.timestamp = clock.unix_timestamp;
-        price_account.last_updater = ctx.accounts.authority.key();
-        price_account.update_count += 1;
-        price_account.bump = ctx.bumps.price_account;
+        let oracle_state = &ctx.accounts.oracle_state;
+        let publisher = ctx.accounts.publisher.key();
+        require!(oracle_state.is_publisher(&publisher), ErrorCode::Unauthorized);
+        require!(
+            ctx.accounts.publisher_account.bonded >= oracle_state.min_bond,
+            ErrorCode::InsufficientBond
+        );
         
-        emit!(PriceUpdated {
-            commodity,
+        let clock = Clock::get()?;
+        let price_account_key = ctx.accounts.price_account.key();
+        let timely = publish_quote(
+            &mut ctx.accounts.price_account,
+            price_account_key,
+            &ctx.accounts.feed_config,
+            oracle_state,
+            &mut *ctx.accounts.price_history.load_mut()?,
+            PublisherQuote {
+                publisher,
+                price,
+                confidence,
+                timestamp: clock.unix_timestamp,
+            },
+            clock.unix_timestamp,
+        )?;
+        if let (true, Some(reward_pool)) = (timely, ctx.accounts.reward_pool.as_mut()) {
+            ctx.accounts.publisher_account.credit_point(reward_pool)?;
+        }
+
+        let price_account = &ctx.accounts.price_account;
+        msg!(
+            "Quote submitted: price={} cents, confidence={}%, aggregate={} cents from {} publishers",
             price,
             confidence,
+            price_account.price,
+            price_account.num_publishers
+        );
+        Ok(())
+    }
+
+    /// Submit quotes for many feeds at one timestamp. Remaining accounts hold a
+    /// (price_account, feed_config, price_history) triple per update, in order.
+    pub fn update_prices<'info>(
+        ctx: Context<'_, '_, 'info, 'info, UpdatePrices<'info>>,
+        updates: Vec<PriceUpdate>,
+    ) -> Result<()> {
+        require!(
+            !updates.is_empty() && updates.len() <= MAX_BATCH_UPDATES,
+            ErrorCode::InvalidBatch
+        );
+        require!(
+            ctx.remaining_accounts.len() == updates.len() * 3,
+            ErrorCode::InvalidBatch
+        );
+        
+        let oracle_state = &ctx.accounts.oracle_state;
+        let publisher = ctx.accounts.publisher.key();
+        require!(oracle_state.is_publisher(&publisher), ErrorCode::Unauthorized);
+        require!(
+            ctx.accounts.publisher_account.bonded >= oracle_state.min_bond,
+            ErrorCode::InsufficientBond
+        );
+        
+        let clock = Clock::get()?;
+        let mut results = Vec::with_capacity(updates.len());
+        let mut points = 0;
+        for (update, accounts) in updates.iter().zip(ctx.remaining_accounts.chunks(3)) {
+            require!(update.price > 0, ErrorCode::InvalidPrice);
+            require!(update.confidence <= 100, ErrorCode::InvalidConfidence);
+            
+            let mut price_account = Account::<PriceAccount>::try_from(&accounts[0])?;
+            let feed_config = Account::<FeedConfig>::try_from(&accounts[1])?;
+            let price_history = AccountLoader::<PriceHistory>::try_from(&accounts[2])?;
+            let price_account_key = price_account.key();
+            require!(
+                price_account.commodity == update.commodity,
+                ErrorCode::InvalidBatch
+            );
+            require_keys_eq!(feed_config.price_account, price_account_key, ErrorCode::InvalidBatch);
+            let mut history = price_history.load_mut()?;
+            require_keys_eq!(history.price_account, price_account_key, ErrorCode::InvalidBatch);
+            
+            let outcome = apply_quote(
+                &mut price_account,
+                price_account_key,
+                &feed_config,
+                oracle_state,
+                &mut history,
+                PublisherQuote {
+                    publisher,
+                    price: update.price,
+                    confidence: update.confidence,
+                    timestamp: clock.unix_timestamp,
+                },
+                clock.unix_timestamp,
+            )?;
+            price_account.exit(&crate::ID)?;
+            
+            if outcome.timely {
+                points += 1;
+            }
+            results.push(FeedUpdate {
+                price_account: price_account_key,
+                price: price_account.price,
+                confidence: price_account.confidence,
+                num_publishers: price_account.num_publishers,
+                accepted: outcome.accepted,
+            });
+        }
+
+        if let Some(reward_pool) = ctx.accounts.reward_pool.as_mut() {
+            for _ in 0..points {
+                ctx.accounts.publisher_account.credit_point(reward_pool)?;
+            }
+        }
+
+        emit!(PricesUpdated {
+            updater: publisher,
             timestamp: clock.unix_timestamp,
-            updater: ctx.accounts.authority.key(),
+            updates: results,
         });
         
-        msg!("Price updated for commodity: price={} cents, confidence={}%", price, confidence);
+        msg!("Batch of {} quotes submitted", updates.len());
         Ok(())
     }
 
-    /// Get price for CPI calls from other programs
-    pub fn get_price(
-        ctx: Context<GetPrice>,
-    ) -> Result<u64> {
-        let price_account = &ctx.accounts.price_account;
-        require!(!price_account.is_stale()?, ErrorCode::StalePrice);
-        Ok(price_account.price)
+    /// Post a publisher-signed price report (permissionless, any relayer can submit)
+    ///
+    /// The instruction immediately before this one must be an ed25519 sig-verify
+    /// of `report.message()` by a registered publisher.
+    pub fn post_signed_price(ctx: Context<PostSignedPrice>, report: PriceReport) -> Result<()> {
+        require!(report.price > 0, ErrorCode::InvalidPrice);
+        require!(report.confidence <= 100, ErrorCode::InvalidConfidence);
+        let price_account_key = ctx.accounts.price_account.key();
+        require_keys_eq!(report.price_account, price_account_key, ErrorCode::InvalidSignedReport);
+        
+        let publisher = ed25519::verify_preceding_signature(
+            &ctx.accounts.instructions_sysvar,
+            &report.message(),
+        )?;
+        let oracle_state = &ctx.accounts.oracle_state;
+        require!(oracle_state.is_publisher(&publisher), ErrorCode::Unauthorized);
+        let publisher_account = &ctx.accounts.publisher_account;
+        require_keys_eq!(publisher_account.publisher, publisher, ErrorCode::InvalidSignedReport);
+        require!(publisher_account.bonded >= oracle_state.min_bond, ErrorCode::InsufficientBond);
+        
+        // Reports must be newer than the publisher's last quote so a posted
+        // report can never be replayed, and recent enough to aggregate
+        let clock = Clock::get()?;
+        require!(
+            report.timestamp > ctx.accounts.price_account.quote_timestamp(&publisher),
+            ErrorCode::ReportReplayed
+        );
+        require!(
+            report.timestamp <= clock.unix_timestamp
+                && clock.unix_timestamp - report.timestamp <= oracle_state.publish_window,
+            ErrorCode::StaleReport
+        );
+        
+        let timely = publish_quote(
+            &mut ctx.accounts.price_account,
+            price_account_key,
+            &ctx.accounts.feed_config,
+            oracle_state,
+            &mut *ctx.accounts.price_history.load_mut()?,
+            PublisherQuote {
+                publisher,
+                price: report.price,
+                confidence: report.confidence,
+                timestamp: report.timestamp,
+            },
+            clock.unix_timestamp,
+        )?;
+        if let (true, Some(reward_pool)) = (timely, ctx.accounts.reward_pool.as_mut()) {
+            ctx.accounts.publisher_account.credit_point(reward_pool)?;
+        }
+
+        msg!(
+            "Signed report posted: publisher={}, price={} cents, aggregate={} cents",
+            publisher,
+            report.price,
+            ctx.accounts.price_account.price
+        );
+        Ok(())
     }
-}
 
-#[derive(Accounts)]
-pub struct Initialize<'info> {
-    #[account(
-        init,
-        payer = authority,
-        space = 8 + OracleState::LEN,
-        seeds = [b"oracle_state"],
-        bump
-    )]
-    pub oracle_state: Account<'info, OracleState>,
-    
-    #[account(mut)]
-    pub authority: Signer<'info>,
-    
-    pub system_program: Program<'info, System>,
-}
+    /// Accept the price that halted a feed and resume it (authority only)
+    pub fn confirm_price(ctx: Context<ConfirmPrice>) -> Result<()> {
+        let price_account = &mut ctx.accounts.price_account;
+        require!(price_account.status == FeedStatus::Halted, ErrorCode::FeedNotHalted);
+        
+        let clock = Clock::get()?;
+        let pending = price_account.pending;
+        price_account.resume(&pending, clock.unix_timestamp);
+        let mut history = ctx.accounts.price_history.load_mut()?;
+        history.record(PriceObservation {
+            price: pending.price,
+            timestamp: clock.unix_timestamp,
+            confidence: pending.confidence,
+            _padding: [0; 7],
+        });
+        price_account.update_volatility(&history);
+        
+        emit!(FeedResumed {
+            price_account: price_account.key(),
+            price: pending.price,
+            confirmed_by: ctx.accounts.authority.key(),
+            timestamp: clock.unix_timestamp,
+        });
+        
+        msg!("Feed resumed by authority at {} cents", pending.price);
+        Ok(())
+    }
 
-#[derive(Accounts)]
-#[instruction(commodity: [u8; 32])]
-pub struct UpdatePrice<'info> {
-    #[account(
-        init_if_needed,
-        payer = authority,
-        space = 8 + PriceAccount::LEN,
-        seeds = [b"price", commodity.as_ref()],
-        bump
-    )]
-    pub price_account: Account<'info, PriceAccount>,
-    
-    #[account(
-        seeds = [b"oracle_state"],
-        bump = oracle_state.bump,
-        has_one = authority @ ErrorCode::Unauthorized
-    )]
-    pub oracle_state: Account<'info, OracleState>,
-    
-    #[account(mut)]
-    pub authority: Signer<'info>,
-    
-    pub system_program: Program<'info, System>,
-}
+    /// Commit to a quote for the feed's current reveal round, opening a new
+    /// round once the previous one is finalized
+    ///
+    /// `commitment` is `commitment_hash` of the quote and a secret salt.
+    pub fn commit_price(ctx: Context<CommitPrice>, commitment: [u8; 32]) -> Result<()> {
+        let feed_config = &ctx.accounts.feed_config;
+        require!(feed_config.uses_commit_reveal(), ErrorCode::CommitRevealDisabled);
+        
+        let oracle_state = &ctx.accounts.oracle_state;
+        let publisher = ctx.accounts.publisher.key();
+        require!(oracle_state.is_publisher(&publisher), ErrorCode::Unauthorized);
+        let publisher_account = &ctx.accounts.publisher_account;
+        require!(
+            publisher_account.bonded >= oracle_state.min_bond,
+            ErrorCode::InsufficientBond
+        );
+        require!(!publisher_account.reveal_flagged, ErrorCode::PublisherFlagged);
+        
+        let clock = Clock::get()?;
+        let price_account_key = ctx.accounts.price_account.key();
+        let round = &mut ctx.accounts.price_round;
+        if round.round_id == 0 || round.finalized {
+            round.open(price_account_key, feed_config, clock.unix_timestamp);
+            round.bump = ctx.bumps.price_round;
+        }
+        require!(clock.unix_timestamp < round.commit_end, ErrorCode::CommitPhaseClosed);
+        round.commit(publisher, commitment)?;
+        
+        emit!(PriceCommitted {
+            price_account: price_account_key,
+            publisher,
+            round_id: round.round_id,
+            commit_end: round.commit_end,
+        });
+        
+        msg!("Quote committed: round={}, publisher={}", round.round_id, publisher);
+        Ok(())
+    }
 
-#[derive(Accounts)]
-pub struct GetPrice<'info> {
-    #[account(
-        seeds = [b"price", price_account.commodity.as_ref()],
-        bump = price_account.bump
-    )]
-    pub price_account: Account<'info, PriceAccount>,
-}
+    /// Reveal a committed quote during the round's reveal phase
+    pub fn reveal_price(
+        ctx: Context<RevealPrice>,
+        price: u64,
+        confidence: u8,
+        salt: [u8; 32],
+    ) -> Result<()> {
+        require!(price > 0, ErrorCode::InvalidPrice);
+        require!(confidence <= 100, ErrorCode::InvalidConfidence);
+        
+        let clock = Clock::get()?;
+        let publisher = ctx.accounts.publisher.key();
+        let round = &mut ctx.accounts.price_round;
+        require!(
+            !round.finalized
+                && clock.unix_timestamp >= round.commit_end
+                && clock.unix_timestamp < round.reveal_end,
+            ErrorCode::NotRevealPhase
+        );
+        round.reveal(publisher, price, confidence, &salt)?;
+        
+        // One point per round, whatever the aggregate ends up being
+        if let Some(reward_pool) = ctx.accounts.reward_pool.as_mut() {
+            ctx.accounts.publisher_account.credit_point(reward_pool)?;
+        }
 
-#[account]
-pub struct OracleState {
-    pub authority: Pubkey,          // 32
-    pub total_commodities: u32,     // 4
-    pub bump: u8,                   // 1
-}
+        msg!("Quote revealed: round={}, price={} cents", round.round_id, price);
+        Ok(())
+    }
 
-impl OracleState {
-    pub const LEN: usize = 32 + 4 + 1;
-}
+    /// Aggregate a round's revealed quotes once its reveal phase is over
+    /// (permissionless). Remaining accounts hold the `PublisherAccount` of every
+    /// publisher that committed without revealing, in commitment order, and
+    /// each is flagged for the missed reveal.
+    pub fn finalize_round<'info>(
+        ctx: Context<'_, '_, 'info, 'info, FinalizeRound<'info>>,
+    ) -> Result<()> {
+        let clock = Clock::get()?;
+        let now = clock.unix_timestamp;
+        let round = &mut ctx.accounts.price_round;
+        require!(round.round_id > 0 && !round.finalized, ErrorCode::RoundAlreadyFinalized);
+        require!(now >= round.reveal_end, ErrorCode::RevealPhaseOpen);
+        
+        let oracle_state = &ctx.accounts.oracle_state;
+        let price_account_key = ctx.accounts.price_account.key();
+        let price_account = &mut ctx.accounts.price_account;
+        
+        // Quotes revealed by publishers removed since the commit are dropped
+        let mut revealed = 0u8;
+        for entry in round.commitments().iter().filter(|c| c.revealed) {
+            if !oracle_state.is_publisher(&entry.publisher) {
+                continue;
+            }
+            price_account.submit_quote(
+                oracle_state.active_publishers(),
+                PublisherQuote {
+                    publisher: entry.publisher,
+                    price: entry.price,
+                    confidence: entry.confidence,
+                    timestamp: now,
+                },
+            )?;
+            price_account.last_updater = entry.publisher;
+            price_account.update_count += 1;
+            revealed += 1;
+        }
 
-#[account]
-pub struct PriceAccount {
-    pub commodity: [u8; 32],        // 32 - "COFFEE", "TEA", etc.
-    pub price: u64,                 // 8 - Price in cents (247 = $2.47)
-    pub confidence: u8,             // 1 - Confidence 0-100
-    pub timestamp: i64,             // 8 - Last update timestamp
-    pub last_updater: Pubkey,       // 32
-    pub update_count: u64,          // 8
-    pub bump: u8,                   // 1
-}
+        let missed: Vec<Pubkey> = round
+            .commitments()
+            .iter()
+            .filter(|c| !c.revealed)
+            .map(|c| c.publisher)
+            .collect();
+        require!(
+            ctx.remaining_accounts.len() == missed.len(),
+            ErrorCode::InvalidMissedReveals
+        );
+        for (publisher, info) in missed.iter().zip(ctx.remaining_accounts) {
+            let mut publisher_account = Account::<PublisherAccount>::try_from(info)?;
+            require_keys_eq!(
+                publisher_account.publisher,
+                *publisher,
+                ErrorCode::InvalidMissedReveals
+            );
+            publisher_account.flag_missed_reveal();
+            publisher_account.exit(&crate::ID)?;
+        }
+        
+        // Earlier rounds' quotes must not stand in for a missed reveal, or for
+        // publishers that skipped this round's commit
+        let revealed_by: Vec<Pubkey> = round
+            .commitments()
+            .iter()
+            .filter(|c| c.revealed)
+            .map(|c| c.publisher)
+            .collect();
+        price_account.expire_quotes_except(&revealed_by);
 
-impl PriceAccount {
-    pub const LEN: usize = 32 + 8 + 1 + 8 + 32 + 8 + 1;
+        let accepted = revealed > 0
+            && settle_aggregate(
+                price_account,
+                price_account_key,
+                &ctx.accounts.feed_config,
+                oracle_state,
+                &mut *ctx.accounts.price_history.load_mut()?,
+                now,
+            );
+        if accepted {
+            emit!(PriceUpdated {
+                commodity: price_account.commodity,
+                price_account: price_account_key,
+                price: price_account.price,
+                confidence: price_account.confidence,
+                num_publishers: price_account.num_publishers,
+                timestamp: now,
+                updater: price_account.last_updater,
+            });
+        }
+        round.finalized = true;
+        
+        emit!(RoundFinalized {
+            price_account: price_account_key,
+            round_id: round.round_id,
+            revealed,
+            missed,
+            accepted,
+            timestamp: now,
+        });
+        
+        msg!(
+            "Round {} finalized: {} reveals, aggregate={} cents",
+            round.round_id,
+            revealed,
+            price_account.price
+        );
+        Ok(())
+    }
+
+    /// Let a publisher flagged for a missed reveal commit again (authority only)
+    pub fn clear_reveal_flag(ctx: Context<ClearRevealFlag>, publisher: Pubkey) -> Result<()> {
+        ctx.accounts.publisher_account.reveal_flagged = false;
+        msg!("Reveal flag cleared for publisher: {}", publisher);
+        Ok(())
+    }
+
+    /// Get price for CPI calls from other programs
+    pub fn get_price(
+        ctx: Context<GetPrice>,
+    ) -> Result<PriceData> {
+        let price_account = &ctx.accounts.price_account;
+        price_account.check_readable(
+            &ctx.accounts.feed_config,
+            ctx.accounts.oracle_state.min_publishers,
+        )?;
+        Ok(PriceData::new(price_account.key(), price_account, &ctx.accounts.feed_config))
+    }
+
+    /// Get a feed price from its primary source, cross-checked against the
+    /// secondary, for CPI calls. Fails if either source is unreadable or stale,
+    /// or if they disagree by more than `max_deviation_bps`.
+    pub fn get_checked_price(ctx: Context<GetCheckedPrice>) -> Result<PriceData> {
+        let price_account = &ctx.accounts.price_account;
+        let feed_config = &ctx.accounts.feed_config;
+        let sources = &ctx.accounts.feed_sources;
+        let min_publishers = ctx.accounts.oracle_state.min_publishers;
+        let clock = Clock::get()?;
+        
+        let primary = external::read_source(
+            &sources.primary,
+            &ctx.accounts.primary_source,
+            price_account,
+            feed_config,
+            min_publishers,
+            clock.unix_timestamp,
+        )?;
+        let secondary = external::read_source(
+            &sources.secondary,
+            &ctx.accounts.secondary_source,
+            price_account,
+            feed_config,
+            min_publishers,
+            clock.unix_timestamp,
+        )?;
+        require!(
+            deviation_bps(primary.price, secondary.price) <= sources.max_deviation_bps as u64,
+            ErrorCode::SourceDeviation
+        );
+        
+        Ok(match sources.primary.kind {
+            PriceSource::Oracle => PriceData::new(price_account.key(), price_account, feed_config),
+            _ => PriceData::from_source(price_account.key(), &primary, feed_config),
+        })
+    }
+
+    /// Get several prices at once for CPI calls. Remaining accounts hold a
+    /// (price_account, feed_config) pair per feed; fails if any feed is unreadable.
+    pub fn get_prices<'info>(
+        ctx: Context<'_, '_, 'info, 'info, GetPrices<'info>>,
+    ) -> Result<Vec<PriceData>> {
+        require!(
+            !ctx.remaining_accounts.is_empty()
+                && ctx.remaining_accounts.len() <= 2 * MAX_PRICE_DATA_READS,
+            ErrorCode::InvalidFeedConfig
+        );
+        consumer::read_feed_pairs(&ctx.accounts.oracle_state, ctx.remaining_accounts)
+    }
+
+    /// Get a feed's price converted into `currency` through an FX feed (CPI view)
+    pub fn get_price_in_currency(
+        ctx: Context<GetPriceInCurrency>,
+        currency: [u8; 8],
+    ) -> Result<DerivedPrice> {
+        let min_publishers = ctx.accounts.oracle_state.min_publishers;
+        let price_account = &ctx.accounts.price_account;
+        let feed_config = &ctx.accounts.feed_config;
+        let fx_price_account = &ctx.accounts.fx_price_account;
+        let fx_feed_config = &ctx.accounts.fx_feed_config;
+        price_account.check_readable(feed_config, min_publishers)?;
+        fx_price_account.check_readable(fx_feed_config, min_publishers)?;
+        
+        let price = convert_price(
+            price_account.price,
+            &feed_config.currency,
+            &currency,
+            fx_price_account.price,
+            fx_feed_config,
+        )?;
+        
+        Ok(DerivedPrice {
+            price,
+            decimals: feed_config.decimals,
+            currency,
+            confidence: (price_account.confidence as u16 * fx_price_account.confidence as u16
+                / 100) as u8,
+            timestamp: price_account.timestamp.min(fx_price_account.timestamp),
+        })
+    }
+
+    /// Get the time-weighted average price over the last `window` seconds (CPI view).
+    /// Fails if the latest price is older than the feed's `max_staleness`.
+    pub fn get_twap(ctx: Context<GetTwap>, window: i64) -> Result<u64> {
+        require!(
+            window > 0 && window <= HISTORY_CAPACITY as i64 * HISTORY_INTERVAL,
+            ErrorCode::InvalidTwapWindow
+        );
+        
+        let price_account = &ctx.accounts.price_account;
+        require!(price_account.status == FeedStatus::Active, ErrorCode::FeedHalted);
+        require!(!price_account.is_retired()?, ErrorCode::FeedRetired);
+        
+        let clock = Clock::get()?;
+        let history = ctx.accounts.price_history.load()?;
+        history.twap(clock.unix_timestamp, window, ctx.accounts.feed_config.max_staleness)
+    }
+
+    /// Get a feed's rolling realized volatility for CPI calls
+    pub fn get_volatility(ctx: Context<GetPrice>) -> Result<VolatilityData> {
+        let price_account = &ctx.accounts.price_account;
+        price_account.check_readable(
+            &ctx.accounts.feed_config,
+            ctx.accounts.oracle_state.min_publishers,
+        )?;
+        require!(
+            price_account.volatility_returns >= 2,
+            ErrorCode::InsufficientHistory
+        );
+        
+        Ok(VolatilityData {
+            feed_id: price_account.key(),
+            volatility_bps: price_account.volatility_bps,
+            num_returns: price_account.volatility_returns,
+            timestamp: price_account.timestamp,
+        })
+    }
+
+    /// Create the forward curve of a feed (authority only)
+    pub fn create_forward_curve(ctx: Context<CreateForwardCurve>) -> Result<()> {
+        let forward_curve = &mut ctx.accounts.forward_curve;
+        forward_curve.price_account = ctx.accounts.price_account.key();
+        forward_curve.bump = ctx.bumps.forward_curve;
+        
+        msg!("Forward curve created for feed: {}", forward_curve.price_account);
+        Ok(())
+    }
+
+    /// Quote delivery months on a feed's forward curve, replacing the publisher's
+    /// earlier quotes for the same months and dropping months already delivered.
+    /// Each month is priced at the median of the publishers' live quotes once a
+    /// quorum has quoted it, under the feed's circuit breaker.
+    pub fn update_forward_curve(
+        ctx: Context<UpdateForwardCurve>,
+        points: Vec<CurveUpdate>,
+    ) -> Result<()> {
+        require!(
+            !points.is_empty() && points.len() <= MAX_TENORS,
+            ErrorCode::InvalidCurve
+        );
+        
+        let oracle_state = &ctx.accounts.oracle_state;
+        let publisher = ctx.accounts.publisher.key();
+        require!(oracle_state.is_publisher(&publisher), ErrorCode::Unauthorized);
+        require!(
+            ctx.accounts.publisher_account.bonded >= oracle_state.min_bond,
+            ErrorCode::InsufficientBond
+        );
+        
+        let clock = Clock::get()?;
+        let forward_curve = &mut ctx.accounts.forward_curve;
+        forward_curve.roll(clock.unix_timestamp);
+        let slot = forward_curve.publisher_slot(oracle_state.active_publishers(), publisher)?;
+        for point in &points {
+            require!(
+                point.price > 0 && point.delivery > clock.unix_timestamp,
+                ErrorCode::InvalidCurve
+            );
+            require!(point.confidence <= 100, ErrorCode::InvalidConfidence);
+            forward_curve.quote(slot, point, clock.unix_timestamp)?;
+        }
+        forward_curve.last_updater = publisher;
+        
+        let halted = forward_curve.settle(
+            oracle_state.active_publishers(),
+            &ctx.accounts.feed_config,
+            oracle_state.min_publishers,
+            oracle_state.publish_window,
+            clock.unix_timestamp,
+        );
+        for tenor in halted {
+            msg!("Delivery {} halted: {} bps move to {}", tenor.delivery, tenor.deviation_bps, tenor.rejected_price);
+            emit!(tenor);
+        }
+        
+        emit!(ForwardCurveUpdated {
+            price_account: forward_curve.price_account,
+            publisher,
+            num_tenors: forward_curve.num_tenors,
+            timestamp: clock.unix_timestamp,
+        });
+        
+        msg!(
+            "Forward curve updated: {} months quoted, {} on the curve",
+            points.len(),
+            forward_curve.num_tenors
+        );
+        Ok(())
+    }
+
+    /// Get the forward price for `delivery` for CPI calls, interpolated linearly
+    /// between the surrounding delivery months
+    pub fn get_forward_price(ctx: Context<GetForwardPrice>, delivery: i64) -> Result<CurvePoint> {
+        let price_account = &ctx.accounts.price_account;
+        require!(price_account.status == FeedStatus::Active, ErrorCode::FeedHalted);
+        require!(!price_account.is_retired()?, ErrorCode::FeedRetired);
+        
+        let clock = Clock::get()?;
+        let point = ctx.accounts.forward_curve.price_at(delivery)?;
+        require!(
+            clock.unix_timestamp - point.timestamp <= ctx.accounts.feed_config.max_staleness,
+            ErrorCode::StalePrice
+        );
+        Ok(point)
+    }
+
+    /// Freeze a feed's settlement price at `fixing_time` (authority, publishers or market registry)
+    ///
+    /// Uses the TWAP over the feed's `fixing_window` ending at `fixing_time`, or
+    /// the aggregate in force at that time if the window is 0. `fixing_time` must
+    /// be a multiple of HISTORY_INTERVAL. A fixing is written once and never modified.
+    pub fn record_fixing(ctx: Context<RecordFixing>, fixing_time: i64) -> Result<()> {
+        require!(fixing_time > 0, ErrorCode::InvalidFixingTime);
+        // Each fixing blocks closing the feed until the authority closes it
+        require!(
+            ctx.accounts.oracle_state.can_record_fixing(&ctx.accounts.payer.key()),
+            ErrorCode::Unauthorized
+        );
+        
+        // The history bucket ending at `fixing_time` takes prints until then
+        let clock = Clock::get()?;
+        require!(clock.unix_timestamp > fixing_time, ErrorCode::FixingNotDue);
+        
+        let price_account = &mut ctx.accounts.price_account;
+        require!(price_account.status == FeedStatus::Active, ErrorCode::FeedHalted);
+        require!(!price_account.is_retired()?, ErrorCode::FeedRetired);
+        
+        // Counted so the feed can't be closed while its fixings are still open
+        price_account.open_fixings = price_account
+            .open_fixings
+            .checked_add(1)
+            .ok_or(ErrorCode::MathOverflow)?;
+        
+        let feed_config = &ctx.accounts.feed_config;
+        let observation = ctx.accounts.price_history.load()?.fixing(
+            fixing_time,
+            feed_config.fixing_window,
+            feed_config.max_staleness,
+        )?;
+        
+        let fixing = &mut ctx.accounts.fixing;
+        fixing.price_account = price_account.key();
+        fixing.fixing_time = fixing_time;
+        fixing.price = observation.price;
+        fixing.confidence = observation.confidence;
+        fixing.window = feed_config.fixing_window;
+        fixing.recorded_at = clock.unix_timestamp;
+        fixing.bump = ctx.bumps.fixing;
+        fixing.payer = ctx.accounts.payer.key();
+        
+        emit!(FixingRecorded {
+            price_account: price_account.key(),
+            fixing_time,
+            price: observation.price,
+            window: feed_config.fixing_window,
+        });
+        
+        msg!("Fixing recorded: time={}, price={} cents", fixing_time, observation.price);
+        Ok(())
+    }
+
+    /// Set the signer (the prediction market's state PDA) that tracks open markets (authority only)
+    pub fn set_market_registry(ctx: Context<UpdateConfig>, market_registry: Pubkey) -> Result<()> {
+        ctx.accounts.oracle_state.market_registry = market_registry;
+        
+        msg!("Market registry set: {}", market_registry);
+        Ok(())
+    }
+
+    /// Count a new market on the feed; fails unless the feed is active (market registry only)
+    pub fn retain_feed(ctx: Context<UpdateFeedReferences>) -> Result<()> {
+        let price_account = &mut ctx.accounts.price_account;
+        require!(price_account.lifecycle == FeedLifecycle::Active, ErrorCode::FeedDeprecated);
+        price_account.open_markets = price_account
+            .open_markets
+            .checked_add(1)
+            .ok_or(ErrorCode::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Stop counting a market once it has resolved (market registry only)
+    pub fn release_feed(ctx: Context<UpdateFeedReferences>) -> Result<()> {
+        let price_account = &mut ctx.accounts.price_account;
+        price_account.open_markets = price_account.open_markets.saturating_sub(1);
+        Ok(())
+    }
+
+    /// Deprecate a feed: reads keep working for `grace_period` seconds, new markets are
+    /// rejected (authority only)
+    pub fn deprecate_feed(ctx: Context<DeprecateFeed>, grace_period: i64) -> Result<()> {
+        require!(grace_period >= 0, ErrorCode::InvalidFeedConfig);
+        let price_account = &mut ctx.accounts.price_account;
+        require!(price_account.lifecycle == FeedLifecycle::Active, ErrorCode::FeedDeprecated);
+        
+        let clock = Clock::get()?;
+        price_account.lifecycle = FeedLifecycle::Deprecated;
+        price_account.retire_at = clock.unix_timestamp + grace_period;
+        
+        emit!(FeedDeprecated {
+            price_account: price_account.key(),
+            retire_at: price_account.retire_at,
+        });
+        
+        msg!("Feed deprecated, retiring at {}", price_account.retire_at);
+        Ok(())
+    }
+
+    /// Close a fixing of a retired feed with no open markets and refund its rent to
+    /// the payer that recorded it (authority only). A feed's fixings must all be
+    /// closed before the feed.
+    pub fn close_fixing(ctx: Context<CloseFixing>) -> Result<()> {
+        let price_account = &mut ctx.accounts.price_account;
+        require!(price_account.is_retired()?, ErrorCode::FeedNotRetired);
+        require!(price_account.open_markets == 0, ErrorCode::FeedHasOpenMarkets);
+        price_account.open_fixings = price_account.open_fixings.saturating_sub(1);
+        
+        msg!("Fixing closed: time={}", ctx.accounts.fixing.fixing_time);
+        Ok(())
+    }
+
+    /// Close a retired feed with no open markets or fixings, along with its round,
+    /// sources and forward curve, and send the rent to `recipient` (authority only).
+    /// The last feed in the index moves into the closed feed's slot.
+    pub fn close_feed(ctx: Context<CloseFeed>) -> Result<()> {
+        let clock = Clock::get()?;
+        ctx.accounts.price_account.check_closable(clock.unix_timestamp)?;
+        
+        let recipient = ctx.accounts.recipient.to_account_info();
+        let accounts = &mut *ctx.accounts;
+        let index = accounts.feed_config.index;
+        match accounts.oracle_state.remove_feed_index(&mut accounts.feed_index, &accounts.last_feed_index)? {
+            // The closed feed held the last slot, which is `last_feed_index` too
+            None => close_account(&accounts.feed_index.to_account_info(), &recipient)?,
+            Some(moved) => {
+                let moved_config = accounts
+                    .last_feed_config
+                    .as_mut()
+                    .ok_or(ErrorCode::WrongFeedIndex)?;
+                require_keys_eq!(moved_config.price_account, moved, ErrorCode::WrongFeedIndex);
+                moved_config.index = index;
+                close_account(&accounts.last_feed_index.to_account_info(), &recipient)?;
+            }
+        }
+        
+        close_if_created(&accounts.price_round, &recipient)?;
+        close_if_created(&accounts.feed_sources, &recipient)?;
+        close_if_created(&accounts.forward_curve, &recipient)?;
+        
+        let price_account = &ctx.accounts.price_account;
+        emit!(FeedClosed {
+            price_account: price_account.key(),
+            recipient: ctx.accounts.recipient.key(),
+        });
+        
+        msg!("Feed closed");
+        Ok(())
+    }
+
+    /// Convert a price account written in an older layout to the current one,
+    /// growing it if needed; the authority pays any extra rent (authority only)
+    pub fn migrate_price_account(ctx: Context<MigratePriceAccount>) -> Result<()> {
+        let account_info = ctx.accounts.price_account.to_account_info();
+        let migrated = migration::upgrade_price_account(&account_info.try_borrow_data()?)?;
+        
+        let new_len = 8 + PriceAccount::LEN;
+        let old_len = account_info.data_len();
+        if new_len > old_len {
+            let rent = Rent::get()?.minimum_balance(new_len);
+            let top_up = rent.saturating_sub(account_info.lamports());
+            if top_up > 0 {
+                system_program::transfer(
+                    CpiContext::new(
+                        ctx.accounts.system_program.to_account_info(),
+                        system_program::Transfer {
+                            from: ctx.accounts.authority.to_account_info(),
+                            to: account_info.clone(),
+                        },
+                    ),
+                    top_up,
+                )?;
+            }
+            account_info.resize(new_len)?;
+        }
+
+        let mut data = account_info.try_borrow_mut_data()?;
+        let mut writer: &mut [u8] = &mut data;
+        migrated.try_serialize(&mut writer)?;
+        
+        emit!(PriceAccountMigrated {
+            price_account: account_info.key(),
+            old_len: old_len as u32,
+            version: PriceAccount::VERSION,
+        });
+        
+        msg!("Price account migrated to version {}", PriceAccount::VERSION);
+        Ok(())
+    }
+
+    /// Move a legacy `[b"price", commodity]` account into the commodity's global
+    /// feed, created beforehand with `create_feed`, and send its rent to
+    /// `recipient` (authority only)
+    pub fn migrate_legacy_price_account(ctx: Context<MigrateLegacyPriceAccount>) -> Result<()> {
+        let legacy_info = ctx.accounts.legacy_price_account.to_account_info();
+        let legacy = migration::LegacyPriceAccount::read(&legacy_info.try_borrow_data()?)?;
+        require_keys_eq!(
+            legacy_info.key(),
+            pda::find_legacy_price_account_address(&legacy.commodity).0,
+            ErrorCode::LegacyFeedMismatch
+        );
+        require!(
+            ctx.accounts.feed_config.decimals == migration::LegacyPriceAccount::DECIMALS,
+            ErrorCode::LegacyFeedMismatch
+        );
+        
+        legacy.move_into(&mut ctx.accounts.price_account)?;
+        let old_len = legacy_info.data_len();
+        close_account(&legacy_info, &ctx.accounts.recipient)?;
+        
+        emit!(PriceAccountMigrated {
+            price_account: ctx.accounts.price_account.key(),
+            old_len: old_len as u32,
+            version: PriceAccount::VERSION,
+        });
+        
+        msg!("Legacy price account moved into {}", ctx.accounts.price_account.key());
+        Ok(())
+    }
+
+    /// Nominate a new authority, which must accept before it takes over (authority only)
+    pub fn propose_authority(ctx: Context<UpdateConfig>, new_authority: Pubkey) -> Result<()> {
+        ctx.accounts.oracle_state.pending_authority = new_authority;
+        
+        msg!("Authority transfer proposed to {}", new_authority);
+        Ok(())
+    }
+
+    /// Complete an authority transfer (pending authority only)
+    pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+        let oracle_state = &mut ctx.accounts.oracle_state;
+        let previous = oracle_state.authority;
+        oracle_state.authority = oracle_state.pending_authority;
+        oracle_state.pending_authority = Pubkey::default();
+        
+        emit!(AuthorityTransferred {
+            previous,
+            new_authority: oracle_state.authority,
+        });
+        
+        msg!("Authority transferred to {}", oracle_state.authority);
+        Ok(())
+    }
+
+    /// Create the M-of-N admin set (oracle authority only). Its PDA becomes an
+    /// authority once nominated with `propose_authority` and accepted through a proposal.
+    pub fn initialize_governance(
+        ctx: Context<InitializeGovernance>,
+        members: Vec<Pubkey>,
+        threshold: u8,
+        timelock: i64,
+    ) -> Result<()> {
+        let governance = &mut ctx.accounts.governance;
+        governance.bump = ctx.bumps.governance;
+        governance.proposal_count = 0;
+        governance.config_version = 0;
+        governance.set_config(&members, threshold, timelock)?;
+        
+        msg!(
+            "Governance initialized: {}-of-{}, timelock={}s",
+            threshold,
+            members.len(),
+            timelock
+        );
+        Ok(())
+    }
+
+    /// Propose an instruction for the governance PDA to sign (members only)
+    pub fn create_proposal(
+        ctx: Context<CreateProposal>,
+        instruction: ProposalInstruction,
+    ) -> Result<()> {
+        require!(
+            instruction.accounts.len() <= MAX_PROPOSAL_ACCOUNTS
+                && instruction.data.len() <= MAX_PROPOSAL_DATA,
+            ErrorCode::ProposalTooLarge
+        );
+        
+        let governance = &mut ctx.accounts.governance;
+        let member = governance
+            .member_index(&ctx.accounts.proposer.key())
+            .ok_or(ErrorCode::NotGovernanceMember)?;
+        
+        let clock = Clock::get()?;
+        let proposal = &mut ctx.accounts.proposal;
+        proposal.id = governance.proposal_count;
+        proposal.proposer = ctx.accounts.proposer.key();
+        proposal.config_version = governance.config_version;
+        proposal.instruction = instruction;
+        proposal.approvals = 0;
+        proposal.approved_at = 0;
+        proposal.executed = false;
+        proposal.bump = ctx.bumps.proposal;
+        proposal.approve(member, governance.threshold, clock.unix_timestamp);
+        governance.proposal_count += 1;
+        
+        emit!(ProposalCreated {
+            proposal: proposal.key(),
+            id: proposal.id,
+            proposer: proposal.proposer,
+            program_id: proposal.instruction.program_id,
+        });
+        
+        msg!("Proposal {} created", proposal.id);
+        Ok(())
+    }
+
+    /// Approve a proposal; the timelock starts once it reaches the threshold (members only)
+    pub fn approve_proposal(ctx: Context<ApproveProposal>) -> Result<()> {
+        let governance = &ctx.accounts.governance;
+        let proposal = &mut ctx.accounts.proposal;
+        require!(!proposal.executed, ErrorCode::ProposalExecuted);
+        require!(
+            proposal.config_version == governance.config_version,
+            ErrorCode::ProposalOutdated
+        );
+        let member = governance
+            .member_index(&ctx.accounts.member.key())
+            .ok_or(ErrorCode::NotGovernanceMember)?;
+        
+        let clock = Clock::get()?;
+        proposal.approve(member, governance.threshold, clock.unix_timestamp);
+        
+        emit!(ProposalApproved {
+            proposal: proposal.key(),
+            member: ctx.accounts.member.key(),
+            approvals: proposal.approvals.count_ones() as u8,
+        });
+        
+        msg!("Proposal {} approved by {}", proposal.id, ctx.accounts.member.key());
+        Ok(())
+    }
+
+    /// Execute an approved proposal after the timelock, signing as the governance PDA.
+    /// Pass the proposal's accounts and target program as remaining accounts.
+    pub fn execute_proposal<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ExecuteProposal<'info>>,
+    ) -> Result<()> {
+        let governance = &ctx.accounts.governance;
+        let proposal = &mut ctx.accounts.proposal;
+        require!(!proposal.executed, ErrorCode::ProposalExecuted);
+        require!(
+            proposal.config_version == governance.config_version,
+            ErrorCode::ProposalOutdated
+        );
+        require!(proposal.approved_at > 0, ErrorCode::ProposalNotApproved);
+        
+        let clock = Clock::get()?;
+        require!(
+            clock.unix_timestamp >= proposal.approved_at + governance.timelock,
+            ErrorCode::TimelockActive
+        );
+        // Persist before the CPI so the proposal can't re-enter and execute twice
+        proposal.executed = true;
+        proposal.exit(&crate::ID)?;
+        
+        let governance_key = governance.key();
+        let instruction = Instruction {
+            program_id: proposal.instruction.program_id,
+            accounts: proposal
+                .instruction
+                .accounts
+                .iter()
+                .map(|a| AccountMeta {
+                    pubkey: a.pubkey,
+                    is_signer: a.is_signer || a.pubkey == governance_key,
+                    is_writable: a.is_writable,
+                })
+                .collect(),
+            data: proposal.instruction.data.clone(),
+        };
+        let seeds = &[b"governance".as_ref(), &[governance.bump]];
+        let signer = &[&seeds[..]];
+        
+        let mut account_infos = ctx.remaining_accounts.to_vec();
+        account_infos.push(ctx.accounts.governance.to_account_info());
+        invoke_signed(&instruction, &account_infos, signer)?;
+        
+        emit!(ProposalExecuted {
+            proposal: ctx.accounts.proposal.key(),
+            id: ctx.accounts.proposal.id,
+        });
+        
+        msg!("Proposal {} executed", ctx.accounts.proposal.id);
+        Ok(())
+    }
+
+    /// Replace the admin set, threshold and timelock (governance PDA only, via a proposal).
+    /// Pending proposals from the old configuration can no longer execute.
+    pub fn set_governance_config(
+        ctx: Context<SetGovernanceConfig>,
+        members: Vec<Pubkey>,
+        threshold: u8,
+        timelock: i64,
+    ) -> Result<()> {
+        let governance = &mut ctx.accounts.governance;
+        governance.set_config(&members, threshold, timelock)?;
+        governance.config_version += 1;
+        
+        msg!(
+            "Governance updated: {}-of-{}, timelock={}s",
+            threshold,
+            members.len(),
+            timelock
+        );
+        Ok(())
+    }
+}
+
+/// Close a program account, moving its rent to `recipient`. Anchor skips writing
+/// back accounts closed this way, as it does for `close = ...`.
+fn close_account(account: &AccountInfo, recipient: &AccountInfo) -> Result<()> {
+    let lamports = account.lamports();
+    **recipient.try_borrow_mut_lamports()? = recipient
+        .lamports()
+        .checked_add(lamports)
+        .ok_or(ErrorCode::MathOverflow)?;
+    **account.try_borrow_mut_lamports()? = 0;
+    account.assign(&system_program::ID);
+    account.resize(0)?;
+    Ok(())
+}
+
+/// Close an optional per-feed PDA (round, sources, curve) if it was ever created
+fn close_if_created(account: &AccountInfo, recipient: &AccountInfo) -> Result<()> {
+    if account.owner == &crate::ID {
+        close_account(account, recipient)?;
+    }
+    Ok(())
+}
+
+/// Store a publisher quote and re-aggregate the feed at `now`. Aggregates that break the
+/// feed's deviation limits halt it instead of being accepted; while halted, quotes
+/// published after the halt from a quorum of publishers resume it.
+///
+/// `timely` is set when the quote is the publisher's first for this feed in the
+/// current heartbeat interval, which earns the publisher a reward point.
+pub fn apply_quote(
+    price_account: &mut PriceAccount,
+    price_account_key: Pubkey,
+    feed_config: &FeedConfig,
+    oracle_state: &OracleState,
+    history: &mut PriceHistory,
+    quote: PublisherQuote,
+    now: i64,
+) -> Result<QuoteOutcome> {
+    require!(!feed_config.uses_commit_reveal(), ErrorCode::CommitRevealRequired);
+    
+    let heartbeat = feed_config.heartbeat;
+    let timely = price_account.quote_timestamp(&quote.publisher) / heartbeat < quote.timestamp / heartbeat;
+    price_account.submit_quote(oracle_state.active_publishers(), quote)?;
+    price_account.last_updater = quote.publisher;
+    price_account.update_count += 1;
+    
+    let accepted = settle_aggregate(price_account, price_account_key, feed_config, oracle_state, history, now);
+    Ok(QuoteOutcome { accepted, timely })
+}
+
+/// Re-aggregate the submitted quotes, running the circuit breaker and recording
+/// history. Returns whether the new aggregate became the feed price.
+pub fn settle_aggregate(
+    price_account: &mut PriceAccount,
+    price_account_key: Pubkey,
+    feed_config: &FeedConfig,
+    oracle_state: &OracleState,
+    history: &mut PriceHistory,
+    now: i64,
+) -> bool {
+    let publishers = oracle_state.active_publishers();
+    if price_account.status == FeedStatus::Halted {
+        // Only quotes strictly after the halt count as confirmations
+        let window = oracle_state.publish_window.min(now - price_account.halted_at - 1);
+        let confirmed = price_account.aggregate(publishers, now, window);
+        if confirmed.num_publishers < oracle_state.min_publishers {
+            price_account.pending = price_account.aggregate(publishers, now, oracle_state.publish_window);
+            return false;
+        }
+
+        price_account.resume(&confirmed, now);
+        emit!(FeedResumed {
+            price_account: price_account_key,
+            price: confirmed.price,
+            confirmed_by: Pubkey::default(),
+            timestamp: now,
+        });
+    } else {
+        let candidate = price_account.aggregate(publishers, now, oracle_state.publish_window);
+        if let Some(deviation_bps) = price_account.breaches_limits(&candidate, feed_config) {
+            price_account.status = FeedStatus::Halted;
+            price_account.halted_at = now;
+            price_account.pending = candidate;
+            emit!(FeedHalted {
+                price_account: price_account_key,
+                last_price: price_account.price,
+                rejected_price: candidate.price,
+                deviation_bps,
+                timestamp: now,
+            });
+            msg!("Feed halted: {} bps move to {} cents", deviation_bps, candidate.price);
+            return false;
+        }
+        price_account.apply(&candidate, now);
+    }
+
+    if price_account.has_quorum(oracle_state.min_publishers) {
+        history.record(PriceObservation {
+            price: price_account.price,
+            timestamp: now,
+            confidence: price_account.confidence,
+            _padding: [0; 7],
+        });
+        price_account.update_volatility(history);
+    }
+
+    true
+}
+
+/// `apply_quote`, emitting `PriceUpdated` when the new aggregate is accepted.
+/// Returns whether the quote earns a reward point.
+pub fn publish_quote(
+    price_account: &mut PriceAccount,
+    price_account_key: Pubkey,
+    feed_config: &FeedConfig,
+    oracle_state: &OracleState,
+    history: &mut PriceHistory,
+    quote: PublisherQuote,
+    now: i64,
+) -> Result<bool> {
+    let outcome = apply_quote(
+        price_account,
+        price_account_key,
+        feed_config,
+        oracle_state,
+        history,
+        quote,
+        now,
+    )?;
+    if outcome.accepted {
+        emit!(PriceUpdated {
+            commodity: price_account.commodity,
+            price_account: price_account_key,
+            price: price_account.price,
+            confidence: price_account.confidence,
+            num_publishers: price_account.num_publishers,
+            timestamp: now,
+            updater: quote.publisher,
+        });
+    }
+    Ok(outcome.timely)
+}
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + OracleState::LEN,
+        seeds = [b"oracle_state"],
+        bump
+    )]
+    pub oracle_state: Account<'info, OracleState>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(publisher: Pubkey)]
+pub struct AddPublisher<'info> {
+    #[account(
+        mut,
+        seeds = [b"oracle_state"],
+        bump = oracle_state.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub oracle_state: Account<'info, OracleState>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + PublisherAccount::LEN,
+        seeds = [b"publisher", publisher.as_ref()],
+        bump
+    )]
+    pub publisher_account: Account<'info, PublisherAccount>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeBonding<'info> {
+    #[account(
+        mut,
+        seeds = [b"oracle_state"],
+        bump = oracle_state.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub oracle_state: Account<'info, OracleState>,
+    
+    pub bond_mint: Account<'info, Mint>,
+    
+    #[account(
+        init,
+        payer = authority,
+        token::mint = bond_mint,
+        token::authority = oracle_state,
+        seeds = [b"bond_vault"],
+        bump
+    )]
+    pub bond_vault: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Bond<'info> {
+    #[account(
+        mut,
+        seeds = [b"publisher", publisher.key().as_ref()],
+        bump = publisher_account.bump
+    )]
+    pub publisher_account: Account<'info, PublisherAccount>,
+    
+    #[account(mut, seeds = [b"bond_vault"], bump)]
+    pub bond_vault: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub publisher_token_account: Account<'info, TokenAccount>,
+    
+    pub publisher: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct RequestUnbond<'info> {
+    #[account(
+        mut,
+        seeds = [b"publisher", publisher.key().as_ref()],
+        bump = publisher_account.bump
+    )]
+    pub publisher_account: Account<'info, PublisherAccount>,
+    
+    #[account(
+        seeds = [b"oracle_state"],
+        bump = oracle_state.bump
+    )]
+    pub oracle_state: Account<'info, OracleState>,
+    
+    pub publisher: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawUnbonded<'info> {
+    #[account(
+        mut,
+        seeds = [b"publisher", publisher.key().as_ref()],
+        bump = publisher_account.bump
+    )]
+    pub publisher_account: Account<'info, PublisherAccount>,
+    
+    #[account(
+        seeds = [b"oracle_state"],
+        bump = oracle_state.bump
+    )]
+    pub oracle_state: Account<'info, OracleState>,
+    
+    #[account(mut, seeds = [b"bond_vault"], bump)]
+    pub bond_vault: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub publisher_token_account: Account<'info, TokenAccount>,
+    
+    pub publisher: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct SlashPublisher<'info> {
+    #[account(
+        seeds = [b"oracle_state"],
+        bump = oracle_state.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub oracle_state: Account<'info, OracleState>,
+    
+    #[account(
+        mut,
+        seeds = [b"publisher", publisher_account.publisher.as_ref()],
+        bump = publisher_account.bump
+    )]
+    pub publisher_account: Account<'info, PublisherAccount>,
+    
+    pub price_account: Account<'info, PriceAccount>,
+    
+    #[account(
+        seeds = [b"history", price_account.key().as_ref()],
+        bump,
+        has_one = price_account
+    )]
+    pub price_history: AccountLoader<'info, PriceHistory>,
+    
+    #[account(mut, seeds = [b"bond_vault"], bump)]
+    pub bond_vault: Account<'info, TokenAccount>,
+    
+    #[account(mut, token::mint = oracle_state.bond_mint)]
+    pub slash_destination: Account<'info, TokenAccount>,
+    
+    /// CHECK: Instructions sysvar, used to find the ed25519 sig-verify instruction
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+    
+    pub authority: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeRewards<'info> {
+    #[account(
+        seeds = [b"oracle_state"],
+        bump = oracle_state.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub oracle_state: Account<'info, OracleState>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + RewardPool::LEN,
+        seeds = [b"reward_pool"],
+        bump
+    )]
+    pub reward_pool: Account<'info, RewardPool>,
+    
+    pub fee_mint: Account<'info, Mint>,
+    
+    #[account(
+        init,
+        payer = authority,
+        token::mint = fee_mint,
+        token::authority = reward_pool,
+        seeds = [b"fee_vault"],
+        bump
+    )]
+    pub fee_vault: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct DepositFees<'info> {
+    #[account(mut, seeds = [b"reward_pool"], bump = reward_pool.bump)]
+    pub reward_pool: Account<'info, RewardPool>,
+    
+    #[account(mut, seeds = [b"fee_vault"], bump)]
+    pub fee_vault: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub depositor_token_account: Account<'info, TokenAccount>,
+    
+    pub depositor: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimRewards<'info> {
+    #[account(mut, seeds = [b"reward_pool"], bump = reward_pool.bump)]
+    pub reward_pool: Account<'info, RewardPool>,
+    
+    #[account(
+        mut,
+        seeds = [b"publisher", publisher.key().as_ref()],
+        bump = publisher_account.bump
+    )]
+    pub publisher_account: Account<'info, PublisherAccount>,
+    
+    #[account(mut, seeds = [b"fee_vault"], bump)]
+    pub fee_vault: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub publisher_token_account: Account<'info, TokenAccount>,
+    
+    pub publisher: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateFeedReferences<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"price",
+            price_account.commodity.as_ref(),
+            price_account.location.as_ref(),
+            price_account.grade.as_ref()
+        ],
+        bump = price_account.bump
+    )]
+    pub price_account: Account<'info, PriceAccount>,
+    
+    #[account(
+        seeds = [b"oracle_state"],
+        bump = oracle_state.bump,
+        has_one = market_registry @ ErrorCode::Unauthorized
+    )]
+    pub oracle_state: Account<'info, OracleState>,
+    
+    pub market_registry: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct DeprecateFeed<'info> {
+    #[account(mut)]
+    pub price_account: Account<'info, PriceAccount>,
+    
+    #[account(
+        seeds = [b"oracle_state"],
+        bump = oracle_state.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub oracle_state: Account<'info, OracleState>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseFeed<'info> {
+    #[account(mut, close = recipient)]
+    pub price_account: Account<'info, PriceAccount>,
+    
+    #[account(
+        mut,
+        close = recipient,
+        seeds = [b"feed_config", price_account.key().as_ref()],
+        bump = feed_config.bump,
+        has_one = price_account
+    )]
+    pub feed_config: Account<'info, FeedConfig>,
+    
+    /// Slot of the closed feed; takes over the last feed unless it is the last slot
+    #[account(
+        mut,
+        seeds = [b"feed_index", feed_config.index.to_le_bytes().as_ref()],
+        bump = feed_index.bump,
+        has_one = price_account
+    )]
+    pub feed_index: Account<'info, FeedIndex>,
+    
+    /// Last slot of the feed index, closed in place of the closed feed's slot
+    #[account(
+        mut,
+        seeds = [b"feed_index", oracle_state.total_commodities.saturating_sub(1).to_le_bytes().as_ref()],
+        bump = last_feed_index.bump
+    )]
+    pub last_feed_index: Account<'info, FeedIndex>,
+    
+    /// Config of the feed in the last slot, required unless that is the closed feed
+    #[account(
+        mut,
+        seeds = [b"feed_config", last_feed_index.price_account.as_ref()],
+        bump = last_feed_config.bump
+    )]
+    pub last_feed_config: Option<Account<'info, FeedConfig>>,
+    
+    #[account(
+        mut,
+        close = recipient,
+        seeds = [b"history", price_account.key().as_ref()],
+        bump,
+        has_one = price_account
+    )]
+    pub price_history: AccountLoader<'info, PriceHistory>,
+    
+    /// CHECK: Commit-reveal round PDA, closed if the feed ever opened one
+    #[account(mut, seeds = [b"round", price_account.key().as_ref()], bump)]
+    pub price_round: UncheckedAccount<'info>,
+    
+    /// CHECK: Sources PDA, closed if the feed has external sources
+    #[account(mut, seeds = [b"sources", price_account.key().as_ref()], bump)]
+    pub feed_sources: UncheckedAccount<'info>,
+    
+    /// CHECK: Forward curve PDA, closed if the feed has a curve
+    #[account(mut, seeds = [b"curve", price_account.key().as_ref()], bump)]
+    pub forward_curve: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"oracle_state"],
+        bump = oracle_state.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub oracle_state: Account<'info, OracleState>,
+    
+    pub authority: Signer<'info>,
+    
+    /// CHECK: Any account chosen by the authority to receive the rent
+    #[account(mut)]
+    pub recipient: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseFixing<'info> {
+    #[account(
+        mut,
+        close = payer,
+        seeds = [b"fixing", price_account.key().as_ref(), &fixing.fixing_time.to_le_bytes()],
+        bump = fixing.bump,
+        has_one = price_account,
+        has_one = payer
+    )]
+    pub fixing: Account<'info, Fixing>,
+    
+    #[account(mut)]
+    pub price_account: Account<'info, PriceAccount>,
+    
+    #[account(
+        seeds = [b"oracle_state"],
+        bump = oracle_state.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub oracle_state: Account<'info, OracleState>,
+    
+    pub authority: Signer<'info>,
+    
+    /// CHECK: Payer that recorded the fixing, refunded its rent; checked by `has_one`
+    #[account(mut)]
+    pub payer: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct MigratePriceAccount<'info> {
+    /// CHECK: Older layouts don't deserialize as `PriceAccount`; the owner and
+    /// discriminator are checked before conversion
+    #[account(mut, owner = crate::ID)]
+    pub price_account: UncheckedAccount<'info>,
+    
+    #[account(
+        seeds = [b"oracle_state"],
+        bump = oracle_state.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub oracle_state: Account<'info, OracleState>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateLegacyPriceAccount<'info> {
+    /// CHECK: Legacy layout doesn't deserialize as `PriceAccount`; the discriminator,
+    /// length and `[b"price", commodity]` address are checked in the handler
+    #[account(mut, owner = crate::ID)]
+    pub legacy_price_account: UncheckedAccount<'info>,
+    
+    /// Global feed of the legacy account's commodity
+    #[account(
+        mut,
+        seeds = [
+            b"price",
+            price_account.commodity.as_ref(),
+            price_account.location.as_ref(),
+            price_account.grade.as_ref()
+        ],
+        bump = price_account.bump
+    )]
+    pub price_account: Account<'info, PriceAccount>,
+    
+    #[account(
+        seeds = [b"feed_config", price_account.key().as_ref()],
+        bump = feed_config.bump,
+        has_one = price_account
+    )]
+    pub feed_config: Account<'info, FeedConfig>,
+    
+    #[account(
+        seeds = [b"oracle_state"],
+        bump = oracle_state.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub oracle_state: Account<'info, OracleState>,
+    
+    pub authority: Signer<'info>,
+    
+    /// CHECK: Any account chosen by the authority to receive the rent
+    #[account(mut)]
+    pub recipient: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [b"oracle_state"],
+        bump = oracle_state.bump,
+        constraint = oracle_state.pending_authority == pending_authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub oracle_state: Account<'info, OracleState>,
+    
+    pub pending_authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeGovernance<'info> {
+    #[account(
+        seeds = [b"oracle_state"],
+        bump = oracle_state.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub oracle_state: Account<'info, OracleState>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Governance::LEN,
+        seeds = [b"governance"],
+        bump
+    )]
+    pub governance: Account<'info, Governance>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CreateProposal<'info> {
+    #[account(mut, seeds = [b"governance"], bump = governance.bump)]
+    pub governance: Account<'info, Governance>,
+    
+    #[account(
+        init,
+        payer = proposer,
+        space = 8 + Proposal::LEN,
+        seeds = [b"proposal", governance.proposal_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+    
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ApproveProposal<'info> {
+    #[account(seeds = [b"governance"], bump = governance.bump)]
+    pub governance: Account<'info, Governance>,
+    
+    #[account(
+        mut,
+        seeds = [b"proposal", proposal.id.to_le_bytes().as_ref()],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+    
+    pub member: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteProposal<'info> {
+    /// Not `mut`, so a proposal that reconfigures governance isn't overwritten on exit
+    #[account(seeds = [b"governance"], bump = governance.bump)]
+    pub governance: Account<'info, Governance>,
+    
+    #[account(
+        mut,
+        seeds = [b"proposal", proposal.id.to_le_bytes().as_ref()],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+    
+    pub executor: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetGovernanceConfig<'info> {
+    #[account(mut, seeds = [b"governance"], bump = governance.bump, signer)]
+    pub governance: Account<'info, Governance>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    #[account(
+        mut,
+        seeds = [b"oracle_state"],
+        bump = oracle_state.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub oracle_state: Account<'info, OracleState>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(feed_key: FeedKey)]
+pub struct CreateFeed<'info> {
+    #[account(
+        mut,
+        seeds = [b"oracle_state"],
+        bump = oracle_state.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub oracle_state: Account<'info, OracleState>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + PriceAccount::LEN,
+        seeds = [
+            b"price",
+            feed_key.commodity.as_ref(),
+            feed_key.location.as_ref(),
+            feed_key.grade.as_ref()
+        ],
+        bump
+    )]
+    pub price_account: Account<'info, PriceAccount>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + FeedConfig::LEN,
+        seeds = [b"feed_config", price_account.key().as_ref()],
+        bump
+    )]
+    pub feed_config: Account<'info, FeedConfig>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + FeedIndex::LEN,
+        seeds = [b"feed_index", oracle_state.total_commodities.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub feed_index: Account<'info, FeedIndex>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + PriceHistory::LEN,
+        seeds = [b"history", price_account.key().as_ref()],
+        bump
+    )]
+    pub price_history: AccountLoader<'info, PriceHistory>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ConfigureFeed<'info> {
+    #[account(
+        mut,
+        seeds = [b"feed_config", price_account.key().as_ref()],
+        bump = feed_config.bump,
+        has_one = price_account
+    )]
+    pub feed_config: Account<'info, FeedConfig>,
+    
+    pub price_account: Account<'info, PriceAccount>,
+    
+    #[account(
+        seeds = [b"oracle_state"],
+        bump = oracle_state.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub oracle_state: Account<'info, OracleState>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetFeedSources<'info> {
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + FeedSources::LEN,
+        seeds = [b"sources", price_account.key().as_ref()],
+        bump
+    )]
+    pub feed_sources: Account<'info, FeedSources>,
+    
+    #[account(
+        seeds = [
+            b"price",
+            price_account.commodity.as_ref(),
+            price_account.location.as_ref(),
+            price_account.grade.as_ref()
+        ],
+        bump = price_account.bump
+    )]
+    pub price_account: Account<'info, PriceAccount>,
+    
+    #[account(
+        seeds = [b"oracle_state"],
+        bump = oracle_state.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub oracle_state: Account<'info, OracleState>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdatePrice<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"price",
+            price_account.commodity.as_ref(),
+            price_account.location.as_ref(),
+            price_account.grade.as_ref()
+        ],
+        bump = price_account.bump
+    )]
+    pub price_account: Account<'info, PriceAccount>,
+    
+    #[account(
+        seeds = [b"oracle_state"],
+        bump = oracle_state.bump
+    )]
+    pub oracle_state: Account<'info, OracleState>,
+    
+    #[account(
+        mut,
+        seeds = [b"history", price_account.key().as_ref()],
+        bump,
+        has_one = price_account
+    )]
+    pub price_history: AccountLoader<'info, PriceHistory>,
+    
+    #[account(
+        seeds = [b"feed_config", price_account.key().as_ref()],
+        bump = feed_config.bump,
+        has_one = price_account
+    )]
+    pub feed_config: Account<'info, FeedConfig>,
+    
+    #[account(
+        mut,
+        seeds = [b"publisher", publisher.key().as_ref()],
+        bump = publisher_account.bump
+    )]
+    pub publisher_account: Account<'info, PublisherAccount>,
+    
+    /// Credits reward points when passed
+    #[account(mut, seeds = [b"reward_pool"], bump = reward_pool.bump)]
+    pub reward_pool: Option<Account<'info, RewardPool>>,
+    
+    pub publisher: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UpdatePrices<'info> {
+    #[account(
+        seeds = [b"oracle_state"],
+        bump = oracle_state.bump
+    )]
+    pub oracle_state: Account<'info, OracleState>,
+    
+    #[account(
+        mut,
+        seeds = [b"publisher", publisher.key().as_ref()],
+        bump = publisher_account.bump
+    )]
+    pub publisher_account: Account<'info, PublisherAccount>,
+    
+    /// Credits reward points when passed
+    #[account(mut, seeds = [b"reward_pool"], bump = reward_pool.bump)]
+    pub reward_pool: Option<Account<'info, RewardPool>>,
+    
+    pub publisher: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct PostSignedPrice<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"price",
+            price_account.commodity.as_ref(),
+            price_account.location.as_ref(),
+            price_account.grade.as_ref()
+        ],
+        bump = price_account.bump
+    )]
+    pub price_account: Account<'info, PriceAccount>,
+    
+    #[account(
+        seeds = [b"oracle_state"],
+        bump = oracle_state.bump
+    )]
+    pub oracle_state: Account<'info, OracleState>,
+    
+    #[account(
+        mut,
+        seeds = [b"history", price_account.key().as_ref()],
+        bump,
+        has_one = price_account
+    )]
+    pub price_history: AccountLoader<'info, PriceHistory>,
+    
+    #[account(
+        seeds = [b"feed_config", price_account.key().as_ref()],
+        bump = feed_config.bump,
+        has_one = price_account
+    )]
+    pub feed_config: Account<'info, FeedConfig>,
+    
+    #[account(
+        mut,
+        seeds = [b"publisher", publisher_account.publisher.as_ref()],
+        bump = publisher_account.bump
+    )]
+    pub publisher_account: Account<'info, PublisherAccount>,
+    
+    /// Credits reward points when passed
+    #[account(mut, seeds = [b"reward_pool"], bump = reward_pool.bump)]
+    pub reward_pool: Option<Account<'info, RewardPool>>,
+    
+    /// CHECK: Instructions sysvar, used to find the ed25519 sig-verify instruction
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ConfirmPrice<'info> {
+    #[account(mut)]
+    pub price_account: Account<'info, PriceAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"history", price_account.key().as_ref()],
+        bump,
+        has_one = price_account
+    )]
+    pub price_history: AccountLoader<'info, PriceHistory>,
+    
+    #[account(
+        seeds = [b"oracle_state"],
+        bump = oracle_state.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub oracle_state: Account<'info, OracleState>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CommitPrice<'info> {
+    #[account(
+        seeds = [
+            b"price",
+            price_account.commodity.as_ref(),
+            price_account.location.as_ref(),
+            price_account.grade.as_ref()
+        ],
+        bump = price_account.bump
+    )]
+    pub price_account: Account<'info, PriceAccount>,
+    
+    #[account(
+        seeds = [b"feed_config", price_account.key().as_ref()],
+        bump = feed_config.bump,
+        has_one = price_account
+    )]
+    pub feed_config: Account<'info, FeedConfig>,
+    
+    #[account(
+        init_if_needed,
+        payer = publisher,
+        space = 8 + PriceRound::LEN,
+        seeds = [b"round", price_account.key().as_ref()],
+        bump
+    )]
+    pub price_round: Account<'info, PriceRound>,
+    
+    #[account(
+        seeds = [b"oracle_state"],
+        bump = oracle_state.bump
+    )]
+    pub oracle_state: Account<'info, OracleState>,
+    
+    #[account(
+        seeds = [b"publisher", publisher.key().as_ref()],
+        bump = publisher_account.bump
+    )]
+    pub publisher_account: Account<'info, PublisherAccount>,
+    
+    #[account(mut)]
+    pub publisher: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevealPrice<'info> {
+    #[account(
+        mut,
+        seeds = [b"round", price_round.price_account.as_ref()],
+        bump = price_round.bump
+    )]
+    pub price_round: Account<'info, PriceRound>,
+    
+    #[account(
+        mut,
+        seeds = [b"publisher", publisher.key().as_ref()],
+        bump = publisher_account.bump
+    )]
+    pub publisher_account: Account<'info, PublisherAccount>,
+    
+    /// Credits a reward point when passed
+    #[account(mut, seeds = [b"reward_pool"], bump = reward_pool.bump)]
+    pub reward_pool: Option<Account<'info, RewardPool>>,
+    
+    pub publisher: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct FinalizeRound<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"price",
+            price_account.commodity.as_ref(),
+            price_account.location.as_ref(),
+            price_account.grade.as_ref()
+        ],
+        bump = price_account.bump
+    )]
+    pub price_account: Account<'info, PriceAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"round", price_account.key().as_ref()],
+        bump = price_round.bump,
+        has_one = price_account
+    )]
+    pub price_round: Account<'info, PriceRound>,
+    
+    #[account(
+        mut,
+        seeds = [b"history", price_account.key().as_ref()],
+        bump,
+        has_one = price_account
+    )]
+    pub price_history: AccountLoader<'info, PriceHistory>,
+    
+    #[account(
+        seeds = [b"feed_config", price_account.key().as_ref()],
+        bump = feed_config.bump,
+        has_one = price_account
+    )]
+    pub feed_config: Account<'info, FeedConfig>,
+    
+    #[account(
+        seeds = [b"oracle_state"],
+        bump = oracle_state.bump
+    )]
+    pub oracle_state: Account<'info, OracleState>,
+}
+
+#[derive(Accounts)]
+#[instruction(publisher: Pubkey)]
+pub struct ClearRevealFlag<'info> {
+    #[account(
+        seeds = [b"oracle_state"],
+        bump = oracle_state.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub oracle_state: Account<'info, OracleState>,
+    
+    #[account(
+        mut,
+        seeds = [b"publisher", publisher.as_ref()],
+        bump = publisher_account.bump
+    )]
+    pub publisher_account: Account<'info, PublisherAccount>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct GetPrice<'info> {
+    #[account(
+        seeds = [
+            b"price",
+            price_account.commodity.as_ref(),
+            price_account.location.as_ref(),
+            price_account.grade.as_ref()
+        ],
+        bump = price_account.bump
+    )]
+    pub price_account: Account<'info, PriceAccount>,
+    
+    #[account(
+        seeds = [b"feed_config", price_account.key().as_ref()],
+        bump = feed_config.bump,
+        has_one = price_account
+    )]
+    pub feed_config: Account<'info, FeedConfig>,
+    
+    #[account(
+        seeds = [b"oracle_state"],
+        bump = oracle_state.bump
+    )]
+    pub oracle_state: Account<'info, OracleState>,
+}
+
+#[derive(Accounts)]
+pub struct GetCheckedPrice<'info> {
+    pub price_account: Account<'info, PriceAccount>,
+    
+    #[account(
+        seeds = [b"feed_config", price_account.key().as_ref()],
+        bump = feed_config.bump,
+        has_one = price_account
+    )]
+    pub feed_config: Account<'info, FeedConfig>,
+    
+    #[account(
+        seeds = [b"sources", price_account.key().as_ref()],
+        bump = feed_sources.bump,
+        has_one = price_account
+    )]
+    pub feed_sources: Account<'info, FeedSources>,
+    
+    #[account(
+        seeds = [b"oracle_state"],
+        bump = oracle_state.bump
+    )]
+    pub oracle_state: Account<'info, OracleState>,
+    
+    /// CHECK: Must be `feed_sources.primary.account`; decoded per its source kind
+    #[account(address = feed_sources.primary.account @ ErrorCode::InvalidSourceAccount)]
+    pub primary_source: UncheckedAccount<'info>,
+    
+    /// CHECK: Must be `feed_sources.secondary.account`; decoded per its source kind
+    #[account(address = feed_sources.secondary.account @ ErrorCode::InvalidSourceAccount)]
+    pub secondary_source: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct GetPrices<'info> {
+    #[account(
+        seeds = [b"oracle_state"],
+        bump = oracle_state.bump
+    )]
+    pub oracle_state: Account<'info, OracleState>,
+}
+
+#[derive(Accounts)]
+pub struct GetPriceInCurrency<'info> {
+    #[account(
+        seeds = [b"feed_config", price_account.key().as_ref()],
+        bump = feed_config.bump,
+        has_one = price_account
+    )]
+    pub feed_config: Account<'info, FeedConfig>,
+    
+    pub price_account: Account<'info, PriceAccount>,
+    
+    #[account(
+        seeds = [b"feed_config", fx_price_account.key().as_ref()],
+        bump = fx_feed_config.bump,
+        constraint = fx_feed_config.price_account == fx_price_account.key()
+    )]
+    pub fx_feed_config: Account<'info, FeedConfig>,
+    
+    pub fx_price_account: Account<'info, PriceAccount>,
+    
+    #[account(
+        seeds = [b"oracle_state"],
+        bump = oracle_state.bump
+    )]
+    pub oracle_state: Account<'info, OracleState>,
+}
+
+#[derive(Accounts)]
+pub struct GetTwap<'info> {
+    #[account(
+        seeds = [
+            b"price",
+            price_account.commodity.as_ref(),
+            price_account.location.as_ref(),
+            price_account.grade.as_ref()
+        ],
+        bump = price_account.bump
+    )]
+    pub price_account: Account<'info, PriceAccount>,
+    
+    #[account(
+        seeds = [b"feed_config", price_account.key().as_ref()],
+        bump = feed_config.bump,
+        has_one = price_account
+    )]
+    pub feed_config: Account<'info, FeedConfig>,
+    
+    #[account(has_one = price_account)]
+    pub price_history: AccountLoader<'info, PriceHistory>,
+}
+
+#[derive(Accounts)]
+#[instruction(fixing_time: i64)]
+pub struct RecordFixing<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Fixing::LEN,
+        seeds = [b"fixing", price_account.key().as_ref(), &fixing_time.to_le_bytes()],
+        bump
+    )]
+    pub fixing: Account<'info, Fixing>,
+    
+    #[account(
+        mut,
+        seeds = [
+            b"price",
+            price_account.commodity.as_ref(),
+            price_account.location.as_ref(),
+            price_account.grade.as_ref()
+        ],
+        bump = price_account.bump
+    )]
+    pub price_account: Account<'info, PriceAccount>,
+    
+    #[account(
+        seeds = [b"history", price_account.key().as_ref()],
+        bump,
+        has_one = price_account
+    )]
+    pub price_history: AccountLoader<'info, PriceHistory>,
+    
+    #[account(
+        seeds = [b"feed_config", price_account.key().as_ref()],
+        bump = feed_config.bump,
+        has_one = price_account
+    )]
+    pub feed_config: Account<'info, FeedConfig>,
+    
+    #[account(
+        seeds = [b"oracle_state"],
+        bump = oracle_state.bump
+    )]
+    pub oracle_state: Account<'info, OracleState>,
+    
+    /// Authority, a publisher or the market registry; refunded when the fixing is closed
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CreateForwardCurve<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + ForwardCurve::LEN,
+        seeds = [b"curve", price_account.key().as_ref()],
+        bump
+    )]
+    pub forward_curve: Box<Account<'info, ForwardCurve>>,
+    
+    #[account(
+        seeds = [
+            b"price",
+            price_account.commodity.as_ref(),
+            price_account.location.as_ref(),
+            price_account.grade.as_ref()
+        ],
+        bump = price_account.bump
+    )]
+    pub price_account: Account<'info, PriceAccount>,
+    
+    #[account(
+        seeds = [b"oracle_state"],
+        bump = oracle_state.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub oracle_state: Account<'info, OracleState>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateForwardCurve<'info> {
+    #[account(
+        mut,
+        seeds = [b"curve", forward_curve.price_account.as_ref()],
+        bump = forward_curve.bump
+    )]
+    pub forward_curve: Box<Account<'info, ForwardCurve>>,
+    
+    #[account(
+        seeds = [b"feed_config", forward_curve.price_account.as_ref()],
+        bump = feed_config.bump
+    )]
+    pub feed_config: Account<'info, FeedConfig>,
+    
+    #[account(
+        seeds = [b"oracle_state"],
+        bump = oracle_state.bump
+    )]
+    pub oracle_state: Account<'info, OracleState>,
+    
+    #[account(
+        seeds = [b"publisher", publisher.key().as_ref()],
+        bump = publisher_account.bump
+    )]
+    pub publisher_account: Account<'info, PublisherAccount>,
+    
+    pub publisher: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct GetForwardPrice<'info> {
+    pub price_account: Account<'info, PriceAccount>,
+    
+    #[account(
+        seeds = [b"feed_config", price_account.key().as_ref()],
+        bump = feed_config.bump,
+        has_one = price_account
+    )]
+    pub feed_config: Account<'info, FeedConfig>,
+    
+    #[account(
+        seeds = [b"curve", price_account.key().as_ref()],
+        bump = forward_curve.bump,
+        has_one = price_account
+    )]
+    pub forward_curve: Box<Account<'info, ForwardCurve>>,
+}
+
+#[account]
+#[derive(Default)]
+pub struct OracleState {
+    pub authority: Pubkey,          // 32
+    pub total_commodities: u32,     // 4 - Live feeds, also the next feed index
+    pub bump: u8,                   // 1
+    pub publishers: [Pubkey; MAX_PUBLISHERS], // 32 * MAX_PUBLISHERS
+    pub publisher_count: u8,        // 1
+    pub min_publishers: u8,         // 1 - Quorum for a valid aggregate
+    pub publish_window: i64,        // 8 - Seconds a quote stays in the aggregate
+    pub bond_mint: Pubkey,          // 32 - Default until bonding is initialized
+    pub min_bond: u64,              // 8 - Bond required to publish
+    pub unbonding_period: i64,      // 8 - Delay before unbonded tokens can be withdrawn
+    pub slash_deviation_bps: u16,   // 2 - Deviation from the aggregate that is slashable
+    pub pending_authority: Pubkey,  // 32 - Nominated by propose_authority, default if none
+    pub market_registry: Pubkey,    // 32 - Signer allowed to count markets on feeds
+}
+
+impl OracleState {
+    pub const LEN: usize = 32 + 4 + 1 + 32 * MAX_PUBLISHERS + 1 + 1 + 8 + 32 + 8 + 8 + 2 + 32 + 32;
     
-    /// Check if price is stale (older than 1 hour)
-    pub fn is_stale(&self) -> Result<bool> {
+    pub fn active_publishers(&self) -> &[Pubkey] {
+        &self.publishers[..self.publisher_count as usize]
+    }
+
+    /// Whether `signer` may record fixings: the authority, a publisher or the market registry
+    pub fn can_record_fixing(&self, signer: &Pubkey) -> bool {
+        *signer == self.authority
+            || *signer == self.market_registry
+            || self.active_publishers().contains(signer)
+    }
+
+    /// Drop the feed in `slot` from the feed index by moving the `last` entry into its
+    /// slot, keeping indexes dense. Returns the moved feed, whose `FeedConfig.index`
+    /// must become `slot.index`; `None` if `slot` was the last entry, left to be closed.
+    pub fn remove_feed_index(&mut self, slot: &mut FeedIndex, last: &FeedIndex) -> Result<Option<Pubkey>> {
+        let last_index = self.total_commodities.checked_sub(1).ok_or(ErrorCode::MathOverflow)?;
+        require!(last.index == last_index, ErrorCode::WrongFeedIndex);
+        self.total_commodities = last_index;
+        if slot.index == last_index {
+            return Ok(None);
+        }
+        slot.price_account = last.price_account;
+        Ok(Some(last.price_account))
+    }
+
+    pub fn is_publisher(&self, key: &Pubkey) -> bool {
+        self.active_publishers().contains(key)
+    }
+
+    pub fn set_bond_requirements(
+        &mut self,
+        min_bond: u64,
+        unbonding_period: i64,
+        slash_deviation_bps: u16,
+    ) -> Result<()> {
+        require!(
+            unbonding_period > 0 && slash_deviation_bps > 0,
+            ErrorCode::InvalidBondConfig
+        );
+        self.min_bond = min_bond;
+        self.unbonding_period = unbonding_period;
+        self.slash_deviation_bps = slash_deviation_bps;
+        Ok(())
+    }
+}
+
+/// M-of-N admin set whose PDA signs the instructions of approved proposals
+#[account]
+pub struct Governance {
+    pub members: Vec<Pubkey>,       // 4 + 32 * MAX_GOVERNANCE_MEMBERS
+    pub threshold: u8,              // 1 - Approvals needed
+    pub timelock: i64,              // 8 - Delay between reaching the threshold and execution
+    pub proposal_count: u64,        // 8
+    pub config_version: u32,        // 4 - Bumped on every config change
+    pub bump: u8,                   // 1
+}
+
+impl Governance {
+    pub const LEN: usize = 4 + 32 * MAX_GOVERNANCE_MEMBERS + 1 + 8 + 8 + 4 + 1;
+    
+    pub fn member_index(&self, key: &Pubkey) -> Option<usize> {
+        self.members.iter().position(|m| m == key)
+    }
+
+    pub fn set_config(&mut self, members: &[Pubkey], threshold: u8, timelock: i64) -> Result<()> {
+        require!(
+            !members.is_empty() && members.len() <= MAX_GOVERNANCE_MEMBERS,
+            ErrorCode::InvalidGovernanceConfig
+        );
+        require!(
+            threshold > 0 && threshold as usize <= members.len() && timelock >= 0,
+            ErrorCode::InvalidGovernanceConfig
+        );
+        for (i, member) in members.iter().enumerate() {
+            require!(!members[..i].contains(member), ErrorCode::InvalidGovernanceConfig);
+        }
+        self.members = members.to_vec();
+        self.threshold = threshold;
+        self.timelock = timelock;
+        Ok(())
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ProposalAccount {
+    pub pubkey: Pubkey,
+    pub is_signer: bool,
+    pub is_writable: bool,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ProposalInstruction {
+    pub program_id: Pubkey,
+    pub accounts: Vec<ProposalAccount>,
+    pub data: Vec<u8>,
+}
+
+impl ProposalInstruction {
+    pub const LEN: usize = 32 + 4 + (32 + 1 + 1) * MAX_PROPOSAL_ACCOUNTS + 4 + MAX_PROPOSAL_DATA;
+}
+
+#[account]
+pub struct Proposal {
+    pub id: u64,                    // 8
+    pub proposer: Pubkey,           // 32
+    pub config_version: u32,        // 4 - Governance config the approvals were given under
+    pub instruction: ProposalInstruction, // ProposalInstruction::LEN
+    pub approvals: u16,             // 2 - Bitmap of member indexes
+    pub approved_at: i64,           // 8 - When the threshold was reached, 0 before
+    pub executed: bool,             // 1
+    pub bump: u8,                   // 1
+}
+
+impl Proposal {
+    pub const LEN: usize = 8 + 32 + 4 + ProposalInstruction::LEN + 2 + 8 + 1 + 1;
+    
+    /// Record `member`'s approval, starting the timelock when `threshold` is first reached
+    pub fn approve(&mut self, member: usize, threshold: u8, now: i64) {
+        self.approvals |= 1 << member;
+        if self.approved_at == 0 && self.approvals.count_ones() >= threshold as u32 {
+            self.approved_at = now;
+        }
+    }
+}
+
+/// Fixed-point scale of `RewardPool.rewards_per_point`
+pub const REWARD_SCALE: u128 = 1_000_000_000_000;
+
+/// Fee vault accounting; its points are the sum of all publishers' unclaimed points
+#[account]
+#[derive(Default)]
+pub struct RewardPool {
+    pub fee_mint: Pubkey,           // 32
+    pub total_points: u64,          // 8
+    pub total_deposited: u64,       // 8
+    pub bump: u8,                   // 1
+    pub rewards_per_point: u128,    // 16 - Cumulative fees per unclaimed point, scaled by REWARD_SCALE
+    pub undistributed: u64,         // 8 - Deposited while no points were unclaimed
+}
+
+impl RewardPool {
+    pub const LEN: usize = 32 + 8 + 8 + 1 + 16 + 8;
+
+    /// Share a deposit among the points unclaimed now, holding it for the
+    /// next deposit if there are none
+    pub fn distribute(&mut self, amount: u64) -> Result<()> {
+        let amount = amount.checked_add(self.undistributed).ok_or(ErrorCode::MathOverflow)?;
+        if self.total_points == 0 {
+            self.undistributed = amount;
+            return Ok(());
+        }
+
+        self.rewards_per_point = self
+            .rewards_per_point
+            .checked_add(amount as u128 * REWARD_SCALE / self.total_points as u128)
+            .ok_or(ErrorCode::MathOverflow)?;
+        self.undistributed = 0;
+        Ok(())
+    }
+}
+
+/// Per-publisher bond and rewards, created when the publisher is first added
+#[account]
+#[derive(Default)]
+pub struct PublisherAccount {
+    pub publisher: Pubkey,          // 32
+    pub bonded: u64,                // 8 - Counts towards `min_bond`
+    pub unbonding: u64,             // 8 - Still slashable until withdrawn
+    pub unbond_requested_at: i64,   // 8
+    pub last_slashed_report: i64,   // 8 - Timestamp of the last report used as evidence
+    pub bump: u8,                   // 1
+    pub reward_points: u64,         // 8 - Unclaimed timely updates, one per feed per heartbeat
+    pub rewards_claimed: u64,       // 8
+    pub missed_reveals: u32,        // 4 - Reveal rounds committed to but not revealed
+    pub reveal_flagged: bool,       // 1 - Set on a missed reveal, blocks commits until cleared
+    pub rewards_per_point_paid: u128, // 16 - `RewardPool.rewards_per_point` when `reward_points` last changed
+    pub rewards_owed: u64,          // 8 - Earned by `reward_points` up to that snapshot
+}
+
+impl PublisherAccount {
+    pub const LEN: usize = 32 + 8 + 8 + 8 + 8 + 1 + 8 + 8 + 4 + 1 + 16 + 8;
+    
+    /// Move what the unclaimed points earned since the last snapshot into `rewards_owed`
+    fn accrue_rewards(&mut self, reward_pool: &RewardPool) -> Result<()> {
+        let earned = (reward_pool.rewards_per_point - self.rewards_per_point_paid)
+            .checked_mul(self.reward_points as u128)
+            .ok_or(ErrorCode::MathOverflow)?
+            / REWARD_SCALE;
+        self.rewards_owed = u64::try_from(earned)
+            .ok()
+            .and_then(|earned| self.rewards_owed.checked_add(earned))
+            .ok_or(ErrorCode::MathOverflow)?;
+        self.rewards_per_point_paid = reward_pool.rewards_per_point;
+        Ok(())
+    }
+
+    /// Credit one point, which earns a share of every later deposit until claimed
+    pub fn credit_point(&mut self, reward_pool: &mut RewardPool) -> Result<()> {
+        self.accrue_rewards(reward_pool)?;
+        self.reward_points = self.reward_points.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
+        reward_pool.total_points = reward_pool
+            .total_points
+            .checked_add(1)
+            .ok_or(ErrorCode::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Retire the unclaimed points and return the fees they earned
+    pub fn claim_rewards(&mut self, reward_pool: &mut RewardPool) -> Result<u64> {
+        self.accrue_rewards(reward_pool)?;
+        let amount = self.rewards_owed;
+        require!(amount > 0, ErrorCode::NoRewards);
+        
+        reward_pool.total_points -= self.reward_points;
+        self.reward_points = 0;
+        self.rewards_owed = 0;
+        self.rewards_claimed += amount;
+        Ok(amount)
+    }
+
+    /// Take up to `amount` from the bond, unbonding tokens first, and return what was taken
+    pub fn slash(&mut self, amount: u64) -> u64 {
+        let from_unbonding = amount.min(self.unbonding);
+        let from_bonded = (amount - from_unbonding).min(self.bonded);
+        self.unbonding -= from_unbonding;
+        self.bonded -= from_bonded;
+        from_unbonding + from_bonded
+    }
+
+    pub fn flag_missed_reveal(&mut self) {
+        self.missed_reveals = self.missed_reveals.saturating_add(1);
+        self.reveal_flagged = true;
+    }
+}
+
+/// Commit-reveal round for a feed: quotes are committed before `commit_end`
+/// and revealed before `reveal_end`, then `finalize_round` aggregates them
+#[account]
+pub struct PriceRound {
+    pub price_account: Pubkey,      // 32
+    pub round_id: u64,              // 8 - 0 until the first round opens
+    pub commit_end: i64,            // 8 - Commits accepted before, reveals from
+    pub reveal_end: i64,            // 8 - Reveals accepted before, finalization from
+    pub entries: [RoundCommitment; MAX_PUBLISHERS], // RoundCommitment::LEN * MAX_PUBLISHERS
+    pub num_entries: u8,            // 1
+    pub finalized: bool,            // 1
+    pub bump: u8,                   // 1
+}
+
+impl PriceRound {
+    pub const LEN: usize = 32 + 8 + 8 + 8 + RoundCommitment::LEN * MAX_PUBLISHERS + 1 + 1 + 1;
+    
+    /// Domain prefix so commitments can't collide with other hashes
+    pub const PREFIX: &'static [u8] = b"afrifutures-oracle:price-commitment:v1";
+    
+    pub fn open(&mut self, price_account: Pubkey, feed_config: &FeedConfig, now: i64) {
+        self.price_account = price_account;
+        self.round_id += 1;
+        self.commit_end = now + feed_config.commit_period;
+        self.reveal_end = self.commit_end + feed_config.reveal_period;
+        self.entries = [RoundCommitment::default(); MAX_PUBLISHERS];
+        self.num_entries = 0;
+        self.finalized = false;
+    }
+
+    pub fn commitments(&self) -> &[RoundCommitment] {
+        &self.entries[..self.num_entries as usize]
+    }
+
+    /// Record a commitment, replacing the publisher's earlier one in this round
+    pub fn commit(&mut self, publisher: Pubkey, commitment: [u8; 32]) -> Result<()> {
+        let index = match self.commitments().iter().position(|c| c.publisher == publisher) {
+            Some(index) => index,
+            None => {
+                require!(
+                    (self.num_entries as usize) < MAX_PUBLISHERS,
+                    ErrorCode::PublisherSetFull
+                );
+                self.num_entries += 1;
+                self.num_entries as usize - 1
+            }
+        };
+        self.entries[index] = RoundCommitment {
+            publisher,
+            commitment,
+            ..Default::default()
+        };
+        Ok(())
+    }
+
+    /// Accept a quote if it matches the publisher's commitment
+    pub fn reveal(&mut self, publisher: Pubkey, price: u64, confidence: u8, salt: &[u8; 32]) -> Result<()> {
+        let expected = commitment_hash(&self.price_account, self.round_id, &publisher, price, confidence, salt);
+        let entry = self.entries[..self.num_entries as usize]
+            .iter_mut()
+            .find(|c| c.publisher == publisher)
+            .ok_or(ErrorCode::NoCommitment)?;
+        require!(!entry.revealed, ErrorCode::AlreadyRevealed);
+        require!(entry.commitment == expected, ErrorCode::CommitmentMismatch);
+        
+        entry.revealed = true;
+        entry.price = price;
+        entry.confidence = confidence;
+        Ok(())
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct RoundCommitment {
+    pub publisher: Pubkey,          // 32
+    pub commitment: [u8; 32],       // 32
+    pub revealed: bool,             // 1
+    pub price: u64,                 // 8 - Set on reveal
+    pub confidence: u8,             // 1
+}
+
+impl RoundCommitment {
+    pub const LEN: usize = 32 + 32 + 1 + 8 + 1;
+}
+
+/// Hash a publisher commits to before revealing a quote. The round and
+/// publisher are bound in, so a copied commitment can't be revealed by
+/// another publisher or carried into a later round.
+pub fn commitment_hash(
+    price_account: &Pubkey,
+    round_id: u64,
+    publisher: &Pubkey,
+    price: u64,
+    confidence: u8,
+    salt: &[u8; 32],
+) -> [u8; 32] {
+    hashv(&[
+        PriceRound::PREFIX,
+        price_account.as_ref(),
+        &round_id.to_le_bytes(),
+        publisher.as_ref(),
+        &price.to_le_bytes(),
+        &[confidence],
+        salt,
+    ])
+    .to_bytes()
+}
+
+#[account]
+pub struct PriceAccount {
+    pub version: u8,                // 1 - Layout version, see `migrate_price_account`
+    pub commodity: [u8; 32],        // 32 - "COFFEE", "TEA", etc.
+    pub location: [u8; 32],         // 32 - "KE:NAIROBI", all zero for the global feed
+    pub grade: [u8; 32],            // 32 - "GRADE_1", "WHITE", all zero if ungraded
+    pub price: u64,                 // 8 - Median price in cents (247 = $2.47)
+    pub confidence: u8,             // 1 - Spread-based confidence 0-100
+    pub timestamp: i64,             // 8 - Last aggregation timestamp
+    pub last_updater: Pubkey,       // 32
+    pub update_count: u64,          // 8
+    pub bump: u8,                   // 1
+    pub num_publishers: u8,         // 1 - Quotes in the current aggregate
+    pub quotes: [PublisherQuote; MAX_PUBLISHERS], // PublisherQuote::LEN * MAX_PUBLISHERS
+    pub status: FeedStatus,         // 1
+    pub halted_at: i64,             // 8
+    pub pending: Aggregate,         // Aggregate::LEN - Latest aggregate while halted
+    pub reference_price: u64,       // 8 - Accepted price at the start of the rate-of-change period
+    pub reference_timestamp: i64,   // 8
+    pub lifecycle: FeedLifecycle,   // 1
+    pub retire_at: i64,             // 8 - End of the deprecation grace period
+    pub open_markets: u32,          // 4 - Unresolved prediction markets on this feed
+    pub volatility_bps: u32,        // 4 - Annualized realized volatility, 10_000 = 100%
+    pub volatility_returns: u8,     // 1 - Log returns behind `volatility_bps`
+    pub open_fixings: u32,          // 4 - Fixings not yet closed, which keep the feed open
+    pub _reserved: [u8; PriceAccount::RESERVED], // Space for new fields without a realloc
+}
+
+impl PriceAccount {
+    pub const VERSION: u8 = 1;
+    pub const RESERVED: usize = 42;
+    pub const LEN: usize = 1
+        + 32 + 32 + 32 + 8 + 1 + 8 + 32 + 8 + 1 + 1 + PublisherQuote::LEN * MAX_PUBLISHERS
+        + 1 + 8 + Aggregate::LEN + 8 + 8 + 1 + 8 + 4 + 4 + 1 + 4 + Self::RESERVED;
+    
+    pub fn feed_key(&self) -> FeedKey {
+        FeedKey {
+            commodity: self.commodity,
+            location: self.location,
+            grade: self.grade,
+        }
+    }
+
+    /// Store a quote in the publisher's slot, reusing slots of removed publishers
+    pub fn submit_quote(&mut self, publishers: &[Pubkey], quote: PublisherQuote) -> Result<()> {
+        let slot = self
+            .quotes
+            .iter()
+            .position(|q| q.publisher == quote.publisher)
+            .or_else(|| self.quotes.iter().position(|q| !publishers.contains(&q.publisher)))
+            .ok_or(ErrorCode::PublisherSetFull)?;
+        self.quotes[slot] = quote;
+        Ok(())
+    }
+
+    /// Timestamp of `publisher`'s stored quote, 0 if it has none
+    pub fn quote_timestamp(&self, publisher: &Pubkey) -> i64 {
+        self.quotes
+            .iter()
+            .find(|q| q.publisher == *publisher)
+            .map_or(0, |q| q.timestamp)
+    }
+
+    /// Refresh the realized volatility after an observation is recorded
+    pub fn update_volatility(&mut self, history: &PriceHistory) {
+        if let Some((volatility_bps, returns)) = history.realized_volatility(VOLATILITY_RETURNS) {
+            self.volatility_bps = volatility_bps;
+            self.volatility_returns = returns;
+        }
+    }
+
+    /// Drop every quote not from `publishers` from future aggregates
+    pub fn expire_quotes_except(&mut self, publishers: &[Pubkey]) {
+        for quote in self.quotes.iter_mut().filter(|q| !publishers.contains(&q.publisher)) {
+            quote.timestamp = 0;
+        }
+    }
+
+    /// Median price and confidence of quotes published within `window` of `now`
+    pub fn aggregate(&self, publishers: &[Pubkey], now: i64, window: i64) -> Aggregate {
+        Aggregate::from_quotes(
+            self.quotes
+                .iter()
+                .filter(|q| {
+                    q.timestamp > 0 && now - q.timestamp <= window && publishers.contains(&q.publisher)
+                })
+                .map(|q| (q.price, q.confidence)),
+        )
+    }
+
+    /// Accept an aggregate as the feed price, keeping the last price if it has no quotes
+    pub fn apply(&mut self, aggregate: &Aggregate, now: i64) {
+        self.num_publishers = aggregate.num_publishers;
+        self.timestamp = now;
+        if aggregate.num_publishers == 0 {
+            return;
+        }
+
+        self.price = aggregate.price;
+        self.confidence = aggregate.confidence;
+        if self.reference_price == 0 || now - self.reference_timestamp >= RATE_OF_CHANGE_PERIOD {
+            self.reference_price = aggregate.price;
+            self.reference_timestamp = now;
+        }
+    }
+
+    /// Lift a halt, accepting `aggregate` and restarting the rate-of-change period from it
+    pub fn resume(&mut self, aggregate: &Aggregate, now: i64) {
+        self.status = FeedStatus::Active;
+        self.pending = Aggregate::default();
+        self.reference_price = 0;
+        self.apply(aggregate, now);
+    }
+
+    /// Move in bps if `candidate` breaks the feed's deviation or rate-of-change limit
+    pub fn breaches_limits(&self, candidate: &Aggregate, feed_config: &FeedConfig) -> Option<u64> {
+        limit_breach(self.price, self.reference_price, candidate, feed_config)
+    }
+
+    pub fn has_quorum(&self, min_publishers: u8) -> bool {
+        self.num_publishers >= min_publishers
+    }
+
+    /// Fail unless the feed is active, not retired, and its aggregate fresh and backed by the quorum
+    pub fn check_readable(&self, feed_config: &FeedConfig, min_publishers: u8) -> Result<()> {
+        require!(self.status == FeedStatus::Active, ErrorCode::FeedHalted);
+        require!(!self.is_retired()?, ErrorCode::FeedRetired);
+        require!(!self.is_stale(feed_config.max_staleness)?, ErrorCode::StalePrice);
+        require!(self.has_quorum(min_publishers), ErrorCode::InsufficientPublishers);
+        Ok(())
+    }
+
+    /// Whether the feed is deprecated and past its grace period
+    pub fn is_retired(&self) -> Result<bool> {
+        let clock = Clock::get()?;
+        Ok(self.lifecycle == FeedLifecycle::Deprecated && clock.unix_timestamp >= self.retire_at)
+    }
+
+    /// Check the feed can be closed at `now`: retired, with no open markets or fixings
+    pub fn check_closable(&self, now: i64) -> Result<()> {
+        require!(
+            self.lifecycle == FeedLifecycle::Deprecated && now >= self.retire_at,
+            ErrorCode::FeedNotRetired
+        );
+        require!(self.open_markets == 0, ErrorCode::FeedHasOpenMarkets);
+        require!(self.open_fixings == 0, ErrorCode::FeedHasFixings);
+        Ok(())
+    }
+
+    /// Check if price is older than the feed's `max_staleness`
+    pub fn is_stale(&self, max_staleness: i64) -> Result<bool> {
         let clock = Clock::get()?;
         let age = clock.unix_timestamp - self.timestamp;
-        Ok(age > 3600) // 1 hour
+        Ok(age > max_staleness)
+    }
+}
+
+/// Identifies a feed: the same commodity can be quoted in several markets and grades
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FeedKey {
+    pub commodity: [u8; 32],        // "MAIZE"
+    pub location: [u8; 32],         // "<ISO country>:<market or exchange>", e.g. "KE:NAIROBI"
+    pub grade: [u8; 32],            // Quality/variety, e.g. "WHITE" or "HARD_WHEAT"
+}
+
+impl FeedKey {
+    /// Build a key from strings, zero-padding (and truncating) each to 32 bytes
+    pub fn new(commodity: &str, location: &str, grade: &str) -> Self {
+        Self {
+            commodity: pad_bytes(commodity),
+            location: pad_bytes(location),
+            grade: pad_bytes(grade),
+        }
+    }
+
+    /// Key of the FX feed quoting one unit of `base` in `quote`, e.g. ("USD", "KES").
+    /// Its feed config should use `base` as unit and `quote` as currency.
+    pub fn fx(base: &str, quote: &str) -> Self {
+        Self::new(&format!("FX:{}/{}", base, quote), "", "")
+    }
+}
+
+/// Feed snapshot returned by `get_price`; check `version` before relying on new fields
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PriceData {
+    pub version: u8,
+    pub feed_id: Pubkey,            // PriceAccount address
+    pub price: u64,
+    pub exponent: i32,              // Real price = price * 10^exponent
+    pub confidence: u8,
+    pub publish_time: i64,
+    pub status: FeedStatus,
+    pub num_publishers: u8,
+    pub update_count: u64,
+}
+
+impl PriceData {
+    pub const VERSION: u8 = 1;
+    
+    pub fn new(feed_id: Pubkey, price_account: &PriceAccount, feed_config: &FeedConfig) -> Self {
+        Self {
+            version: Self::VERSION,
+            feed_id,
+            price: price_account.price,
+            exponent: -(feed_config.decimals as i32),
+            confidence: price_account.confidence,
+            publish_time: price_account.timestamp,
+            status: price_account.status,
+            num_publishers: price_account.num_publishers,
+            update_count: price_account.update_count,
+        }
+    }
+
+    /// Snapshot priced by a third-party source; it has no publishers or update count
+    pub fn from_source(
+        feed_id: Pubkey,
+        source: &external::SourcePrice,
+        feed_config: &FeedConfig,
+    ) -> Self {
+        Self {
+            version: Self::VERSION,
+            feed_id,
+            price: source.price,
+            exponent: -(feed_config.decimals as i32),
+            confidence: source.confidence,
+            publish_time: source.timestamp,
+            status: FeedStatus::Active,
+            num_publishers: 0,
+            update_count: 0,
+        }
+    }
+}
+
+/// Realized volatility returned by `get_volatility`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct VolatilityData {
+    pub feed_id: Pubkey,            // PriceAccount address
+    pub volatility_bps: u32,        // Annualized, 10_000 = 100%
+    pub num_returns: u8,            // Log returns between history observations
+    pub timestamp: i64,             // Latest price included
+}
+
+/// A feed price re-quoted in another currency
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DerivedPrice {
+    pub price: u64,                 // In `currency`, with the source feed's decimals
+    pub decimals: u8,
+    pub currency: [u8; 8],
+    pub confidence: u8,             // Product of both inputs' confidence
+    pub timestamp: i64,             // Older of the two inputs
+}
+
+/// Whether an FX feed converts between `from` and `to`, in either direction
+pub fn fx_pairs(fx_config: &FeedConfig, from: &[u8; 8], to: &[u8; 8]) -> bool {
+    (fx_base_is(fx_config, from) && fx_config.currency == *to)
+        || (fx_config.currency == *from && fx_base_is(fx_config, to))
+}
+
+fn fx_base_is(fx_config: &FeedConfig, currency: &[u8; 8]) -> bool {
+    fx_config.unit[..8] == currency[..] && fx_config.unit[8..].iter().all(|b| *b == 0)
+}
+
+/// Convert `price` from `from` into `to` currency using an FX rate quoted by `fx_config`
+/// (price of one `fx_config.unit` in `fx_config.currency`); either direction works
+pub fn convert_price(
+    price: u64,
+    from: &[u8; 8],
+    to: &[u8; 8],
+    fx_rate: u64,
+    fx_config: &FeedConfig,
+) -> Result<u64> {
+    require!(fx_pairs(fx_config, from, to), ErrorCode::CurrencyMismatch);
+    require!(fx_rate > 0, ErrorCode::InvalidFxRate);
+    
+    let scale = 10u128.pow(fx_config.decimals as u32);
+    let converted = if fx_base_is(fx_config, from) {
+        price as u128 * fx_rate as u128 / scale
+    } else {
+        price as u128 * scale / fx_rate as u128
+    };
+    u64::try_from(converted).map_err(|_| error!(ErrorCode::MathOverflow))
+}
+
+pub fn pad_bytes<const N: usize>(value: &str) -> [u8; N] {
+    let mut bytes = [0u8; N];
+    let len = value.len().min(N);
+    bytes[..len].copy_from_slice(&value.as_bytes()[..len]);
+    bytes
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct PublisherQuote {
+    pub publisher: Pubkey,          // 32
+    pub price: u64,                 // 8
+    pub confidence: u8,             // 1
+    pub timestamp: i64,             // 8
+}
+
+impl PublisherQuote {
+    pub const LEN: usize = 32 + 8 + 1 + 8;
+}
+
+#[account]
+pub struct FeedConfig {
+    pub price_account: Pubkey,      // 32
+    pub commodity: [u8; 32],        // 32
+    pub index: u32,                 // 4 - Position in the feed index
+    pub unit: [u8; 16],             // 16 - "MT", "90KG_BAG", "KG"
+    pub currency: [u8; 8],          // 8 - "USD", "KES"
+    pub decimals: u8,               // 1 - 2 means prices are in cents
+    pub heartbeat: i64,             // 8 - Expected seconds between updates
+    pub max_staleness: i64,         // 8 - Reads fail once the price is older
+    pub created_at: i64,            // 8
+    pub bump: u8,                   // 1
+    pub max_deviation_bps: u16,     // 2 - Max move per update, 0 disables
+    pub max_hourly_change_bps: u16, // 2 - Max move per RATE_OF_CHANGE_PERIOD, 0 disables
+    pub commit_period: i64,         // 8 - Commit phase of a reveal round, 0 takes plaintext quotes
+    pub reveal_period: i64,         // 8 - Reveal phase following the commit phase
+    pub fixing_window: i64,         // 8 - TWAP window ending at each fixing, 0 fixes the spot aggregate
+}
+
+impl FeedConfig {
+    pub const LEN: usize = 32 + 32 + 4 + 16 + 8 + 1 + 8 + 8 + 8 + 1 + 2 + 2 + 8 + 8 + 8;
+    
+    /// Quotes only enter the aggregate through commit-reveal rounds
+    pub fn uses_commit_reveal(&self) -> bool {
+        self.commit_period > 0
+    }
+
+    pub fn apply(&mut self, params: &FeedParams) {
+        self.unit = params.unit;
+        self.currency = params.currency;
+        self.decimals = params.decimals;
+        self.heartbeat = params.heartbeat;
+        self.max_staleness = params.max_staleness;
+        self.max_deviation_bps = params.max_deviation_bps;
+        self.max_hourly_change_bps = params.max_hourly_change_bps;
+        self.commit_period = params.commit_period;
+        self.reveal_period = params.reveal_period;
+        self.fixing_window = params.fixing_window;
+    }
+
+    /// Plain feed with a 300s heartbeat and 3600s max staleness; tests override the rest
+    #[cfg(any(test, feature = "test-utils"))]
+    pub fn test(price_account: Pubkey, decimals: u8) -> Self {
+        Self {
+            price_account,
+            commodity: [0u8; 32],
+            index: 0,
+            unit: [0u8; 16],
+            currency: [0u8; 8],
+            decimals,
+            heartbeat: 300,
+            max_staleness: 3600,
+            created_at: 0,
+            bump: 0,
+            max_deviation_bps: 0,
+            max_hourly_change_bps: 0,
+            commit_period: 0,
+            reveal_period: 0,
+            fixing_window: 0,
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct FeedParams {
+    pub unit: [u8; 16],
+    pub currency: [u8; 8],
+    pub decimals: u8,
+    pub heartbeat: i64,
+    pub max_staleness: i64,
+    pub max_deviation_bps: u16,
+    pub max_hourly_change_bps: u16,
+    pub commit_period: i64,
+    pub reveal_period: i64,
+    pub fixing_window: i64,
+}
+
+impl FeedParams {
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.heartbeat > 0 && self.max_staleness >= self.heartbeat,
+            ErrorCode::InvalidFeedConfig
+        );
+        require!(self.decimals <= MAX_DECIMALS, ErrorCode::InvalidFeedConfig);
+        require!(
+            self.commit_period >= 0
+                && self.reveal_period >= 0
+                && (self.commit_period > 0) == (self.reveal_period > 0),
+            ErrorCode::InvalidFeedConfig
+        );
+        require!(
+            self.fixing_window >= 0 && self.fixing_window <= HISTORY_CAPACITY as i64 * HISTORY_INTERVAL,
+            ErrorCode::InvalidFeedConfig
+        );
+        Ok(())
+    }
+}
+
+/// Enumerates feeds: entry `i` lives at `[b"feed_index", i.to_le_bytes()]`
+/// for `i < OracleState.total_commodities`. Closing a feed moves the last entry
+/// into its slot, so indexes stay dense and a feed's index can change.
+#[account]
+#[derive(Default)]
+pub struct FeedIndex {
+    pub index: u32,                 // 4
+    pub price_account: Pubkey,      // 32
+    pub bump: u8,                   // 1
+}
+
+impl FeedIndex {
+    pub const LEN: usize = 4 + 32 + 1;
+}
+
+/// Frozen settlement price of a feed at a scheduled time, at
+/// `[b"fixing", price_account, fixing_time.to_le_bytes()]`
+#[account]
+pub struct Fixing {
+    pub price_account: Pubkey,      // 32
+    pub fixing_time: i64,           // 8
+    pub price: u64,                 // 8
+    pub confidence: u8,             // 1 - Of the aggregate in force at `fixing_time`
+    pub window: i64,                // 8 - TWAP window, 0 for the spot aggregate
+    pub recorded_at: i64,           // 8
+    pub bump: u8,                   // 1
+    pub payer: Pubkey,              // 32 - Recorded the fixing, refunded its rent on close
+}
+
+impl Fixing {
+    pub const LEN: usize = 32 + 8 + 8 + 1 + 8 + 8 + 1 + 32;
+}
+
+/// Primary and secondary sources compared by `get_checked_price`
+#[account]
+pub struct FeedSources {
+    pub price_account: Pubkey,      // 32
+    pub primary: SourceRef,         // SourceRef::LEN
+    pub secondary: SourceRef,       // SourceRef::LEN
+    pub max_deviation_bps: u16,     // 2 - Max disagreement between the two
+    pub bump: u8,                   // 1
+}
+
+impl FeedSources {
+    pub const LEN: usize = 32 + SourceRef::LEN * 2 + 2 + 1;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SourceRef {
+    pub kind: PriceSource,          // 1
+    pub account: Pubkey,            // 32 - The price account itself for `Oracle`
+}
+
+impl SourceRef {
+    pub const LEN: usize = 1 + 32;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PriceSource {
+    /// This oracle's own aggregate
+    #[default]
+    Oracle,
+    /// Pyth receiver `PriceUpdateV2`
+    Pyth,
+    /// Switchboard On-Demand pull feed
+    Switchboard,
+}
+
+/// Term structure of a feed: prices for its delivery months, sorted by delivery
+#[account]
+pub struct ForwardCurve {
+    pub price_account: Pubkey,      // 32
+    pub num_tenors: u8,             // 1
+    pub tenors: [Tenor; MAX_TENORS], // Tenor::LEN * MAX_TENORS
+    pub publishers: [Pubkey; MAX_PUBLISHERS], // 32 * MAX_PUBLISHERS - Publisher quoting in each `Tenor.quotes` slot
+    pub last_updater: Pubkey,       // 32
+    pub bump: u8,                   // 1
+}
+
+impl ForwardCurve {
+    pub const LEN: usize = 32 + 1 + Tenor::LEN * MAX_TENORS + 32 * MAX_PUBLISHERS + 32 + 1;
+    
+    pub fn tenors(&self) -> &[Tenor] {
+        &self.tenors[..self.num_tenors as usize]
+    }
+
+    /// Drop months delivered before `now`
+    pub fn roll(&mut self, now: i64) {
+        let len = self.num_tenors as usize;
+        let delivered = self.tenors().iter().take_while(|t| t.point.delivery < now).count();
+        self.tenors.copy_within(delivered..len, 0);
+        for tenor in &mut self.tenors[len - delivered..len] {
+            *tenor = Tenor::default();
+        }
+        self.num_tenors -= delivered as u8;
+    }
+
+    /// Quote slot of `publisher`, taking over the slot of a removed publisher if it has none
+    pub fn publisher_slot(&mut self, publishers: &[Pubkey], publisher: Pubkey) -> Result<usize> {
+        if let Some(slot) = self.publishers.iter().position(|p| *p == publisher) {
+            return Ok(slot);
+        }
+        let slot = self
+            .publishers
+            .iter()
+            .position(|p| !publishers.contains(p))
+            .ok_or(ErrorCode::PublisherSetFull)?;
+        self.publishers[slot] = publisher;
+        for tenor in &mut self.tenors {
+            tenor.quotes[slot] = TenorQuote::default();
+        }
+        Ok(slot)
+    }
+
+    /// Store a publisher's quote for a delivery month, adding the month if it is new
+    pub fn quote(&mut self, slot: usize, update: &CurveUpdate, now: i64) -> Result<()> {
+        let len = self.num_tenors as usize;
+        let index = match self.tenors().binary_search_by_key(&update.delivery, |t| t.point.delivery) {
+            Ok(index) => index,
+            Err(index) => {
+                require!(len < MAX_TENORS, ErrorCode::CurveFull);
+                self.tenors.copy_within(index..len, index + 1);
+                self.tenors[index] = Tenor::default();
+                self.tenors[index].point.delivery = update.delivery;
+                self.num_tenors += 1;
+                index
+            }
+        };
+        
+        let tenor = &mut self.tenors[index];
+        tenor.point.contract = update.contract;
+        tenor.quotes[slot] = TenorQuote {
+            price: update.price,
+            confidence: update.confidence,
+            timestamp: now,
+        };
+        Ok(())
+    }
+
+    /// Re-aggregate every month from the quotes of current `publishers`,
+    /// returning the months the circuit breaker halted
+    pub fn settle(
+        &mut self,
+        publishers: &[Pubkey],
+        feed_config: &FeedConfig,
+        min_publishers: u8,
+        publish_window: i64,
+        now: i64,
+    ) -> Vec<TenorHalted> {
+        let active = self.publishers.map(|p| publishers.contains(&p));
+        let price_account = self.price_account;
+        let len = self.num_tenors as usize;
+        self.tenors[..len]
+            .iter_mut()
+            .filter_map(|tenor| {
+                let last_price = tenor.point.price;
+                let (rejected_price, deviation_bps) =
+                    tenor.settle(&active, feed_config, min_publishers, publish_window, now)?;
+                Some(TenorHalted {
+                    price_account,
+                    delivery: tenor.point.delivery,
+                    last_price,
+                    rejected_price,
+                    deviation_bps,
+                    timestamp: now,
+                })
+            })
+            .collect()
+    }
+
+    /// Price for `delivery`, interpolated linearly in time between the surrounding
+    /// priced months. Confidence and timestamp are the weaker of the two.
+    pub fn price_at(&self, delivery: i64) -> Result<CurvePoint> {
+        let priced: Vec<&Tenor> = self.tenors().iter().filter(|t| t.is_priced()).collect();
+        let index = match priced.binary_search_by_key(&delivery, |t| t.point.delivery) {
+            Ok(index) => {
+                require!(priced[index].halted_at == 0, ErrorCode::FeedHalted);
+                return Ok(priced[index].point);
+            }
+            Err(index) => index,
+        };
+        require!(
+            index > 0 && index < priced.len(),
+            ErrorCode::DeliveryOutsideCurve
+        );
+        
+        let (before, after) = (priced[index - 1], priced[index]);
+        require!(before.halted_at == 0 && after.halted_at == 0, ErrorCode::FeedHalted);
+        let (before, after) = (&before.point, &after.point);
+        let elapsed = (delivery - before.delivery) as i128;
+        let span = (after.delivery - before.delivery) as i128;
+        let price = before.price as i128 + (after.price as i128 - before.price as i128) * elapsed / span;
+        Ok(CurvePoint {
+            contract: [0u8; 8],
+            delivery,
+            price: price as u64,
+            confidence: before.confidence.min(after.confidence),
+            timestamp: before.timestamp.min(after.timestamp),
+        })
+    }
+}
+
+/// A delivery month: its accepted price and each publisher's latest quote
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Tenor {
+    pub point: CurvePoint,          // CurvePoint::LEN - Accepted price, timestamp 0 until a quorum quotes
+    pub quotes: [TenorQuote; MAX_PUBLISHERS], // TenorQuote::LEN * MAX_PUBLISHERS
+    pub num_publishers: u8,         // 1 - Quotes in the accepted price
+    pub reference_price: u64,       // 8 - Accepted price at the start of the rate-of-change period
+    pub reference_timestamp: i64,   // 8
+    pub halted_at: i64,             // 8 - When the circuit breaker halted the month, 0 while active
+}
+
+impl Tenor {
+    pub const LEN: usize = CurvePoint::LEN + TenorQuote::LEN * MAX_PUBLISHERS + 1 + 8 + 8 + 8;
+
+    /// Whether a quorum has ever priced the month
+    pub fn is_priced(&self) -> bool {
+        self.point.timestamp > 0
+    }
+
+    /// Re-aggregate the month like `settle_aggregate` does a spot feed, except that
+    /// only an aggregate backed by `min_publishers` live quotes is accepted. A move
+    /// past the feed's limits halts the month until a quorum quotes after the halt.
+    /// Returns the rejected price and its move in bps if the month was halted.
+    pub fn settle(
+        &mut self,
+        active: &[bool; MAX_PUBLISHERS],
+        feed_config: &FeedConfig,
+        min_publishers: u8,
+        publish_window: i64,
+        now: i64,
+    ) -> Option<(u64, u64)> {
+        let halted = self.halted_at > 0;
+        // Only quotes strictly after a halt count as confirmations
+        let window = if halted {
+            publish_window.min(now - self.halted_at - 1)
+        } else {
+            publish_window
+        };
+        let candidate = Aggregate::from_quotes(
+            self.quotes
+                .iter()
+                .zip(active)
+                .filter(|(q, active)| **active && q.timestamp > 0 && now - q.timestamp <= window)
+                .map(|(q, _)| (q.price, q.confidence)),
+        );
+        if candidate.num_publishers == 0 || candidate.num_publishers < min_publishers {
+            return None;
+        }
+
+        if halted {
+            self.halted_at = 0;
+            self.reference_price = 0;
+        } else if let Some(deviation_bps) =
+            limit_breach(self.point.price, self.reference_price, &candidate, feed_config)
+        {
+            self.halted_at = now;
+            return Some((candidate.price, deviation_bps));
+        }
+        
+        self.point.price = candidate.price;
+        self.point.confidence = candidate.confidence;
+        self.point.timestamp = now;
+        self.num_publishers = candidate.num_publishers;
+        if self.reference_price == 0 || now - self.reference_timestamp >= RATE_OF_CHANGE_PERIOD {
+            self.reference_price = candidate.price;
+            self.reference_timestamp = now;
+        }
+        None
+    }
+}
+
+/// A publisher's quote for a delivery month
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TenorQuote {
+    pub price: u64,                 // 8
+    pub confidence: u8,             // 1
+    pub timestamp: i64,             // 8 - 0 if the slot has no quote
+}
+
+impl TenorQuote {
+    pub const LEN: usize = 8 + 1 + 8;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CurvePoint {
+    pub contract: [u8; 8],          // 8 - "DEC25", all zero for interpolated prices
+    pub delivery: i64,              // 8 - Delivery (contract expiry) timestamp
+    pub price: u64,                 // 8 - In the feed's currency and decimals
+    pub confidence: u8,             // 1
+    pub timestamp: i64,             // 8 - When the month's price was last accepted
+}
+
+impl CurvePoint {
+    pub const LEN: usize = 8 + 8 + 8 + 1 + 8;
+}
+
+/// One delivery month in an `update_forward_curve` call
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CurveUpdate {
+    pub contract: [u8; 8],
+    pub delivery: i64,
+    pub price: u64,
+    pub confidence: u8,
+}
+
+#[account(zero_copy)]
+pub struct PriceHistory {
+    pub price_account: Pubkey,      // 32
+    pub head: u32,                  // 4 - Next write index
+    pub len: u32,                   // 4
+    pub observations: [PriceObservation; HISTORY_CAPACITY], // 24 * HISTORY_CAPACITY
+}
+
+impl PriceHistory {
+    pub const LEN: usize = 32 + 4 + 4 + PriceObservation::LEN * HISTORY_CAPACITY;
+    
+    /// Observation `i` in chronological order (0 = oldest retained)
+    pub fn get(&self, i: usize) -> &PriceObservation {
+        let oldest = self.head as usize + HISTORY_CAPACITY - self.len as usize;
+        &self.observations[(oldest + i) % HISTORY_CAPACITY]
+    }
+
+    pub fn latest(&self) -> Option<&PriceObservation> {
+        (self.len > 0).then(|| self.get(self.len as usize - 1))
+    }
+
+    /// Bucket of `timestamp`. Buckets are `(k * HISTORY_INTERVAL, (k + 1) * HISTORY_INTERVAL]`,
+    /// so the observation a bucket keeps is the one in force at its closing boundary.
+    pub fn bucket(timestamp: i64) -> i64 {
+        (timestamp - 1).div_euclid(HISTORY_INTERVAL)
+    }
+
+    /// Append an observation, replacing the latest one if both fall in the same bucket
+    pub fn record(&mut self, observation: PriceObservation) {
+        if let Some(latest) = self.latest() {
+            if Self::bucket(latest.timestamp) == Self::bucket(observation.timestamp) {
+                let index = (self.head as usize + HISTORY_CAPACITY - 1) % HISTORY_CAPACITY;
+                self.observations[index] = observation;
+                return;
+            }
+        }
+
+        self.observations[self.head as usize] = observation;
+        self.head = ((self.head as usize + 1) % HISTORY_CAPACITY) as u32;
+        if (self.len as usize) < HISTORY_CAPACITY {
+            self.len += 1;
+        }
+    }
+
+    /// Observation recorded in the same bucket as `timestamp`, if still retained
+    pub fn observation_in_bucket(&self, timestamp: i64) -> Option<&PriceObservation> {
+        let bucket = Self::bucket(timestamp);
+        (0..self.len as usize)
+            .map(|i| self.get(i))
+            .find(|o| Self::bucket(o.timestamp) == bucket)
+    }
+
+    /// Latest observation at or before `timestamp`, if still retained
+    pub fn observation_at(&self, timestamp: i64) -> Option<&PriceObservation> {
+        (0..self.len as usize)
+            .map(|i| self.get(i))
+            .take_while(|o| o.timestamp <= timestamp)
+            .last()
+    }
+
+    /// Settlement observation at `fixing_time`: the TWAP over `window` or, for a
+    /// zero window, the aggregate then in force. The aggregate in force must be
+    /// no older than `max_staleness`. Only bucket boundaries are fixed, as a print
+    /// later in the same bucket replaces the one in force at an earlier time.
+    pub fn fixing(&self, fixing_time: i64, window: i64, max_staleness: i64) -> Result<PriceObservation> {
+        require!(fixing_time % HISTORY_INTERVAL == 0, ErrorCode::InvalidFixingTime);
+        let spot = *self
+            .observation_at(fixing_time)
+            .ok_or(ErrorCode::InsufficientHistory)?;
+        require!(
+            fixing_time - spot.timestamp <= max_staleness,
+            ErrorCode::StalePrice
+        );
+        if window == 0 {
+            return Ok(spot);
+        }
+        Ok(PriceObservation {
+            price: self.twap(fixing_time, window, max_staleness)?,
+            ..spot
+        })
+    }
+
+    /// Annualized realized volatility in bps from the sample stdev of the last
+    /// `returns` log returns between observations, with the number of returns
+    /// used; needs at least two. Observations are irregular, so the stdev is
+    /// annualized by their mean interval.
+    pub fn realized_volatility(&self, returns: usize) -> Option<(u32, u8)> {
+        let len = self.len as usize;
+        let count = returns.min(len.saturating_sub(1));
+        if count < 2 {
+            return None;
+        }
+        let first = len - count - 1;
+        let elapsed = self.get(len - 1).timestamp - self.get(first).timestamp;
+        if elapsed <= 0 {
+            return None;
+        }
+
+        let log_returns: Vec<i128> = (first..len - 1)
+            .map(|i| log_return(self.get(i).price, self.get(i + 1).price))
+            .collect();
+        let n = count as i128;
+        let mean = log_returns.iter().sum::<i128>() / n;
+        let variance = log_returns.iter().map(|r| (r - mean).pow(2)).sum::<i128>() / (n - 1);
+        
+        // Variance per return times returns per year
+        let annual = variance * SECONDS_PER_YEAR as i128 * n / elapsed as i128;
+        let volatility_bps = isqrt(annual as u128) * 10_000 / LOG_SCALE as u128;
+        Some((volatility_bps.min(u32::MAX as u128) as u32, count as u8))
+    }
+
+    /// Time-weighted average price over `[end - window, end]`; each observation
+    /// holds until the next one, so the latest price is carried forward to `end`.
+    /// Fails if that price is older than `max_staleness` at `end`, or if the
+    /// history covers less than half the window.
+    pub fn twap(&self, end: i64, window: i64, max_staleness: i64) -> Result<u64> {
+        let start = end - window;
+        let len = self.len as usize;
+        let mut weighted: u128 = 0;
+        let mut covered: i64 = 0;
+        let mut last_timestamp = None;
+        
+        for i in 0..len {
+            let observation = self.get(i);
+            if observation.timestamp > end {
+                break;
+            }
+            last_timestamp = Some(observation.timestamp);
+            let until = if i + 1 < len {
+                self.get(i + 1).timestamp.min(end)
+            } else {
+                end
+            };
+            let from = observation.timestamp.max(start);
+            if until > from {
+                weighted += observation.price as u128 * (until - from) as u128;
+                covered += until - from;
+            }
+        }
+
+        let last_timestamp = last_timestamp.ok_or(ErrorCode::InsufficientHistory)?;
+        require!(end - last_timestamp <= max_staleness, ErrorCode::StalePrice);
+        require!(covered > 0 && covered * 2 >= window, ErrorCode::InsufficientHistory);
+        Ok((weighted / covered as u128) as u64)
+    }
+}
+
+#[zero_copy]
+pub struct PriceObservation {
+    pub price: u64,                 // 8
+    pub timestamp: i64,             // 8
+    pub confidence: u8,             // 1
+    pub _padding: [u8; 7],          // 7
+}
+
+impl PriceObservation {
+    pub const LEN: usize = 8 + 8 + 1 + 7;
+}
+
+/// One feed's quote in an `update_prices` batch
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PriceUpdate {
+    pub commodity: [u8; 32],
+    pub price: u64,
+    pub confidence: u8,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct QuoteOutcome {
+    /// The new aggregate became the feed price (false while the feed is halted)
+    pub accepted: bool,
+    pub timely: bool,
+}
+
+/// Price report signed off-chain by a publisher
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PriceReport {
+    pub price_account: Pubkey,
+    pub price: u64,
+    pub confidence: u8,
+    pub timestamp: i64,
+}
+
+impl PriceReport {
+    /// Domain prefix so report signatures can't be reused as other messages
+    pub const PREFIX: &'static [u8] = b"afrifutures-oracle:price-report:v1";
+    
+    /// Bytes the publisher signs
+    pub fn message(&self) -> Vec<u8> {
+        let mut message = Self::PREFIX.to_vec();
+        message.extend_from_slice(self.price_account.as_ref());
+        message.extend_from_slice(&self.price.to_le_bytes());
+        message.push(self.confidence);
+        message.extend_from_slice(&self.timestamp.to_le_bytes());
+        message
+    }
+}
+
+/// Feeds are closed from `Deprecated` once the grace period is over and no
+/// market references them; closed feeds no longer exist on chain
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FeedLifecycle {
+    #[default]
+    Active,
+    /// Still readable until `retire_at`, but no new markets
+    Deprecated,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FeedStatus {
+    #[default]
+    Active,
+    /// An update broke the deviation limits; reads fail until confirmed
+    Halted,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Aggregate {
+    pub price: u64,                 // 8
+    pub confidence: u8,             // 1
+    pub num_publishers: u8,         // 1
+}
+
+impl Aggregate {
+    pub const LEN: usize = 8 + 1 + 1;
+
+    /// Median price and confidence of `(price, confidence)` quotes
+    pub fn from_quotes(quotes: impl Iterator<Item = (u64, u8)>) -> Self {
+        let (mut prices, mut confidences): (Vec<u64>, Vec<u64>) =
+            quotes.map(|(price, confidence)| (price, confidence as u64)).unzip();
+        if prices.is_empty() {
+            return Self::default();
+        }
+
+        let median_price = median(&mut prices);
+        
+        // Confidence drops one point per percent of spread between the
+        // lowest and highest quote, capped by what publishers reported
+        let spread = prices[prices.len() - 1] - prices[0];
+        let spread_pct = if median_price == 0 {
+            100
+        } else {
+            (spread as u128 * 100 / median_price as u128).min(100) as u64
+        };
+        
+        Self {
+            price: median_price,
+            confidence: (100 - spread_pct).min(median(&mut confidences)) as u8,
+            num_publishers: prices.len() as u8,
+        }
+    }
+}
+
+/// Move in bps if `candidate` moves more than the feed's deviation limit from `price`,
+/// or more than its rate-of-change limit from `reference_price`
+pub fn limit_breach(
+    price: u64,
+    reference_price: u64,
+    candidate: &Aggregate,
+    feed_config: &FeedConfig,
+) -> Option<u64> {
+    if candidate.num_publishers == 0 || price == 0 {
+        return None;
+    }
+
+    let move_bps = deviation_bps(price, candidate.price);
+    if feed_config.max_deviation_bps > 0 && move_bps > feed_config.max_deviation_bps as u64 {
+        return Some(move_bps);
+    }
+
+    if feed_config.max_hourly_change_bps > 0 && reference_price > 0 {
+        let change_bps = deviation_bps(reference_price, candidate.price);
+        if change_bps > feed_config.max_hourly_change_bps as u64 {
+            return Some(change_bps);
+        }
     }
+    None
+}
+
+/// Relative move from `from` to `to` in basis points
+pub fn deviation_bps(from: u64, to: u64) -> u64 {
+    (from.abs_diff(to) as u128 * 10_000 / from as u128).min(u64::MAX as u128) as u64
+}
+
+/// Fixed-point scale of `log_return` (1.0 = LOG_SCALE)
+pub const LOG_SCALE: i128 = 1_000_000_000_000;
+
+/// `ln(to / from)` scaled by LOG_SCALE, from the series
+/// `2 * (z + z^3/3 + z^5/5 + ...)` with `z = (to - from) / (to + from)`;
+/// within 1e-7 for moves up to 2x
+pub fn log_return(from: u64, to: u64) -> i128 {
+    if from == 0 || to == 0 {
+        return 0;
+    }
+    let z = (to as i128 - from as i128) * LOG_SCALE / (to as i128 + from as i128);
+    let z2 = z * z / LOG_SCALE;
+    let mut term = z;
+    let mut sum = 0;
+    for k in [1, 3, 5, 7, 9, 11, 13] {
+        sum += term / k;
+        term = term * z2 / LOG_SCALE;
+    }
+    2 * sum
+}
+
+/// Floor of the square root of `value`, by Newton's method
+pub fn isqrt(value: u128) -> u128 {
+    if value < 2 {
+        return value;
+    }
+    let mut x = value / 2 + 1;
+    let mut y = (x + value / x) / 2;
+    while y < x {
+        x = y;
+        y = (x + value / x) / 2;
+    }
+    x
+}
+
+/// Median of `values`, sorting them in place (mean of the middle pair for even counts)
+pub fn median(values: &mut [u64]) -> u64 {
+    values.sort_unstable();
+    let mid = values.len() / 2;
+    if values.len() % 2 == 1 {
+        values[mid]
+    } else {
+        ((values[mid - 1] as u128 + values[mid] as u128) / 2) as u64
+    }
+}
+
+#[event]
+pub struct PriceUpdated {
+    pub commodity: [u8; 32],
+    pub price_account: Pubkey,
+    pub price: u64,
+    pub confidence: u8,
+    pub num_publishers: u8,
+    pub timestamp: i64,
+    pub updater: Pubkey,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FeedUpdate {
+    pub price_account: Pubkey,
+    pub price: u64,
+    pub confidence: u8,
+    pub num_publishers: u8,
+    pub accepted: bool,
+}
+
+#[event]
+pub struct PricesUpdated {
+    pub updater: Pubkey,
+    pub timestamp: i64,
+    pub updates: Vec<FeedUpdate>,
+}
+
+#[event]
+pub struct FeedHalted {
+    pub price_account: Pubkey,
+    pub last_price: u64,
+    pub rejected_price: u64,
+    pub deviation_bps: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct FeedResumed {
+    pub price_account: Pubkey,
+    pub price: u64,
+    pub confirmed_by: Pubkey,       // Authority, or default when a publisher quorum confirmed
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PublisherBonded {
+    pub publisher: Pubkey,
+    pub amount: u64,
+    pub bonded: u64,
+}
+
+#[event]
+pub struct UnbondRequested {
+    pub publisher: Pubkey,
+    pub amount: u64,
+    pub available_at: i64,
+}
+
+#[event]
+pub struct PublisherSlashed {
+    pub publisher: Pubkey,
+    pub price_account: Pubkey,
+    pub reported_price: u64,
+    pub aggregate_price: u64,
+    pub deviation_bps: u64,
+    pub amount: u64,
+}
+
+#[event]
+pub struct FeesDeposited {
+    pub depositor: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct RewardsClaimed {
+    pub publisher: Pubkey,
+    pub points: u64,
+    pub amount: u64,
+}
+
+#[event]
+pub struct FeedDeprecated {
+    pub price_account: Pubkey,
+    pub retire_at: i64,
+}
+
+#[event]
+pub struct FeedClosed {
+    pub price_account: Pubkey,
+    pub recipient: Pubkey,
+}
+
+#[event]
+pub struct PriceAccountMigrated {
+    pub price_account: Pubkey,
+    pub old_len: u32,
+    pub version: u8,
 }
 
 #[event]
-pub struct PriceUpdated {
-    pub commodity: [u8; 32],
+pub struct AuthorityTransferred {
+    pub previous: Pubkey,
+    pub new_authority: Pubkey,
+}
+
+#[event]
+pub struct ProposalCreated {
+    pub proposal: Pubkey,
+    pub id: u64,
+    pub proposer: Pubkey,
+    pub program_id: Pubkey,
+}
+
+#[event]
+pub struct ProposalApproved {
+    pub proposal: Pubkey,
+    pub member: Pubkey,
+    pub approvals: u8,
+}
+
+#[event]
+pub struct ProposalExecuted {
+    pub proposal: Pubkey,
+    pub id: u64,
+}
+
+#[event]
+pub struct PriceCommitted {
+    pub price_account: Pubkey,
+    pub publisher: Pubkey,
+    pub round_id: u64,
+    pub commit_end: i64,
+}
+
+#[event]
+pub struct RoundFinalized {
+    pub price_account: Pubkey,
+    pub round_id: u64,
+    pub revealed: u8,
+    /// Publishers flagged for committing without revealing
+    pub missed: Vec<Pubkey>,
+    pub accepted: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct FixingRecorded {
+    pub price_account: Pubkey,
+    pub fixing_time: i64,
     pub price: u64,
-    pub confidence: u8,
+    pub window: i64,
+}
+
+#[event]
+pub struct ForwardCurveUpdated {
+    pub price_account: Pubkey,
+    pub publisher: Pubkey,
+    pub num_tenors: u8,
     pub timestamp: i64,
-    pub updater: Pubkey,
+}
+
+#[event]
+pub struct TenorHalted {
+    pub price_account: Pubkey,
+    pub delivery: i64,
+    pub last_price: u64,
+    pub rejected_price: u64,
+    pub deviation_bps: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct FeedCreated {
+    pub commodity: [u8; 32],
+    pub location: [u8; 32],
+    pub grade: [u8; 32],
+    pub price_account: Pubkey,
+    pub index: u32,
+}
+
+#[event]
+pub struct PublisherAdded {
+    pub publisher: Pubkey,
+    pub publisher_count: u8,
+}
+
+#[event]
+pub struct PublisherRemoved {
+    pub publisher: Pubkey,
+    pub publisher_count: u8,
 }
 
 #[error_code]
 pub enum ErrorCode {
-    #[msg("Unauthorized: Only oracle authority can update prices")]
+    #[msg("Unauthorized: Only oracle authority or registered publishers can do this")]
     Unauthorized,
     
     #[msg("Invalid confidence: must be 0-100")]
@@ -162,4 +4514,1014 @@ pub enum ErrorCode {
     
     #[msg("Stale price: oracle data is too old")]
     StalePrice,
+    
+    #[msg("Invalid publisher key")]
+    InvalidPublisher,
+    
+    #[msg("Publisher is already registered")]
+    PublisherExists,
+    
+    #[msg("Publisher is not registered")]
+    PublisherNotFound,
+    
+    #[msg("Publisher set is full")]
+    PublisherSetFull,
+    
+    #[msg("Invalid quorum: min publishers must be 1..=MAX_PUBLISHERS and window positive")]
+    InvalidQuorum,
+    
+    #[msg("Not enough publishers in the current window")]
+    InsufficientPublishers,
+    
+    #[msg("Invalid TWAP window: must be positive and within the history span")]
+    InvalidTwapWindow,
+    
+    #[msg("Not enough price history in the requested window")]
+    InsufficientHistory,
+    
+    #[msg("Invalid feed config: check heartbeat, max staleness and decimals")]
+    InvalidFeedConfig,
+    
+    #[msg("Feed decimals cannot change once prices have been published")]
+    FeedHasPrices,
+    
+    #[msg("FX feed does not quote the requested currency pair")]
+    CurrencyMismatch,
+    
+    #[msg("Invalid FX rate")]
+    InvalidFxRate,
+    
+    #[msg("Math overflow")]
+    MathOverflow,
+    
+    #[msg("Invalid price: must be greater than 0")]
+    InvalidPrice,
+    
+    #[msg("Feed is halted by the circuit breaker")]
+    FeedHalted,
+    
+    #[msg("Feed is not halted")]
+    FeedNotHalted,
+    
+    #[msg("Missing ed25519 signature verification instruction")]
+    MissingSignatureVerification,
+    
+    #[msg("Signed report does not match the verified signature")]
+    InvalidSignedReport,
+    
+    #[msg("Report is not newer than the publisher's last quote")]
+    ReportReplayed,
+    
+    #[msg("Report timestamp is in the future or outside the publish window")]
+    StaleReport,
+    
+    #[msg("Bonding has already been initialized")]
+    BondingAlreadyInitialized,
+    
+    #[msg("Bonding has not been initialized")]
+    BondingNotInitialized,
+    
+    #[msg("Invalid bond config: unbonding period and slash threshold must be greater than 0")]
+    InvalidBondConfig,
+    
+    #[msg("Invalid bond amount")]
+    InvalidBondAmount,
+    
+    #[msg("Publisher bond is below the minimum")]
+    InsufficientBond,
+    
+    #[msg("Unbonding period has not passed")]
+    UnbondingPeriodActive,
+    
+    #[msg("Report is within the slashing tolerance")]
+    ReportWithinTolerance,
+    
+    #[msg("Invalid fee amount")]
+    InvalidFeeAmount,
+    
+    #[msg("No reward points to claim")]
+    NoRewards,
+    
+    #[msg("Invalid governance config: need 1..=10 unique members and a threshold within them")]
+    InvalidGovernanceConfig,
+    
+    #[msg("Signer is not a governance member")]
+    NotGovernanceMember,
+    
+    #[msg("Proposal instruction exceeds the account or data limit")]
+    ProposalTooLarge,
+    
+    #[msg("Proposal has already been executed")]
+    ProposalExecuted,
+    
+    #[msg("Proposal was created under a previous governance config")]
+    ProposalOutdated,
+    
+    #[msg("Proposal has not reached the approval threshold")]
+    ProposalNotApproved,
+    
+    #[msg("Proposal timelock has not passed")]
+    TimelockActive,
+    
+    #[msg("Batch updates must be non-empty, within the limit and match the remaining accounts")]
+    InvalidBatch,
+    
+    #[msg("Account layout version is not recognized")]
+    UnknownAccountVersion,
+    
+    #[msg("Account is already at the current layout version")]
+    AlreadyMigrated,
+    
+    #[msg("Legacy price accounts are moved with migrate_legacy_price_account")]
+    LegacyLayout,
+    
+    #[msg("Legacy price can only move into the unpriced, cents-denominated global feed of its commodity")]
+    LegacyFeedMismatch,
+    
+    #[msg("Feed is deprecated")]
+    FeedDeprecated,
+    
+    #[msg("Feed is past its deprecation grace period")]
+    FeedRetired,
+    
+    #[msg("Feed must be deprecated and past its grace period to close")]
+    FeedNotRetired,
+    
+    #[msg("Feed is still referenced by open markets")]
+    FeedHasOpenMarkets,
+    
+    #[msg("Feed still has fixings that must be closed first")]
+    FeedHasFixings,
+    
+    #[msg("Feed index accounts do not match the last feed")]
+    WrongFeedIndex,
+    
+    #[msg("Feed takes quotes through commit-reveal rounds")]
+    CommitRevealRequired,
+    
+    #[msg("Feed does not use commit-reveal rounds")]
+    CommitRevealDisabled,
+    
+    #[msg("Round is past its commit phase")]
+    CommitPhaseClosed,
+    
+    #[msg("Round is not in its reveal phase")]
+    NotRevealPhase,
+    
+    #[msg("Round is still in its reveal phase")]
+    RevealPhaseOpen,
+    
+    #[msg("Round has already been finalized")]
+    RoundAlreadyFinalized,
+    
+    #[msg("No commitment from this publisher in the current round")]
+    NoCommitment,
+    
+    #[msg("Quote has already been revealed")]
+    AlreadyRevealed,
+    
+    #[msg("Revealed quote does not match the commitment")]
+    CommitmentMismatch,
+    
+    #[msg("Publisher is flagged for a missed reveal")]
+    PublisherFlagged,
+    
+    #[msg("Remaining accounts must be the publisher accounts of every missed reveal, in order")]
+    InvalidMissedReveals,
+    
+    #[msg("Invalid fixing time")]
+    InvalidFixingTime,
+    
+    #[msg("Fixing time has not passed yet")]
+    FixingNotDue,
+    
+    #[msg("Account is not a valid, fully verified price source of its kind")]
+    InvalidSourceAccount,
+    
+    #[msg("Invalid price sources: need two distinct sources and a deviation limit")]
+    InvalidSourceConfig,
+    
+    #[msg("Primary and secondary sources disagree beyond the deviation limit")]
+    SourceDeviation,
+    
+    #[msg("Curve updates need 1..=12 future delivery months with positive prices")]
+    InvalidCurve,
+    
+    #[msg("Forward curve has no free delivery months")]
+    CurveFull,
+    
+    #[msg("Delivery is outside the months on the forward curve")]
+    DeliveryOutsideCurve,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quote(publisher: Pubkey, price: u64, confidence: u8, timestamp: i64) -> PublisherQuote {
+        PublisherQuote {
+            publisher,
+            price,
+            confidence,
+            timestamp,
+        }
+    }
+
+    fn empty_price_account() -> PriceAccount {
+        PriceAccount {
+            version: PriceAccount::VERSION,
+            commodity: [0u8; 32],
+            location: [0u8; 32],
+            grade: [0u8; 32],
+            price: 0,
+            confidence: 0,
+            timestamp: 0,
+            last_updater: Pubkey::default(),
+            update_count: 0,
+            bump: 0,
+            num_publishers: 0,
+            quotes: [PublisherQuote::default(); MAX_PUBLISHERS],
+            status: FeedStatus::Active,
+            halted_at: 0,
+            pending: Aggregate::default(),
+            reference_price: 0,
+            reference_timestamp: 0,
+            lifecycle: FeedLifecycle::Active,
+            retire_at: 0,
+            open_markets: 0,
+            volatility_bps: 0,
+            volatility_returns: 0,
+            open_fixings: 0,
+            _reserved: [0; PriceAccount::RESERVED],
+        }
+    }
+
+    #[test]
+    fn test_median() {
+        assert_eq!(median(&mut [300, 100, 200]), 200);
+        assert_eq!(median(&mut [400, 100, 300, 200]), 250);
+        assert_eq!(median(&mut [u64::MAX, u64::MAX]), u64::MAX);
+    }
+
+    #[test]
+    fn test_isqrt() {
+        for value in [0u128, 1, 2, 3, 4, 15, 16, 17, 1 << 64, u128::MAX] {
+            let root = isqrt(value);
+            assert!(root * root <= value);
+            if let Some(next) = (root + 1).checked_mul(root + 1) {
+                assert!(next > value);
+            }
+        }
+        assert_eq!(isqrt(1_000_000), 1_000);
+    }
+
+    #[test]
+    fn test_aggregate_uses_live_quotes_from_registered_publishers() {
+        let publishers: Vec<Pubkey> = (0..4).map(|_| Pubkey::new_unique()).collect();
+        let removed = Pubkey::new_unique();
+        let mut account = empty_price_account();
+        
+        account.submit_quote(&publishers, quote(publishers[0], 250, 95, 1_000)).unwrap();
+        account.submit_quote(&publishers, quote(publishers[1], 260, 90, 1_100)).unwrap();
+        account.submit_quote(&publishers, quote(publishers[2], 255, 99, 1_150)).unwrap();
+        // Outside the window
+        account.submit_quote(&publishers, quote(publishers[3], 900, 99, 500)).unwrap();
+        account.quotes[4] = quote(removed, 1, 100, 1_150);
+        
+        let aggregate = account.aggregate(&publishers, 1_200, 300);
+        account.apply(&aggregate, 1_200);
+        
+        assert_eq!(account.num_publishers, 3);
+        assert_eq!(account.price, 255);
+        // 10 cent spread on 255 is 3%
+        assert_eq!(account.confidence, 95);
+        assert!(account.has_quorum(3));
+        assert!(!account.has_quorum(4));
+    }
+
+    #[test]
+    fn test_round_keeps_only_revealed_quotes() {
+        let publishers: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+        let mut account = empty_price_account();
+        
+        // publishers[2] quoted last round but skipped this round's commit
+        account.submit_quote(&publishers, quote(publishers[2], 300, 99, 1_000)).unwrap();
+        account.submit_quote(&publishers, quote(publishers[0], 250, 95, 1_200)).unwrap();
+        account.submit_quote(&publishers, quote(publishers[1], 260, 95, 1_200)).unwrap();
+        assert_eq!(account.aggregate(&publishers, 1_200, 300).num_publishers, 3);
+        
+        account.expire_quotes_except(&publishers[..2]);
+        let aggregate = account.aggregate(&publishers, 1_200, 300);
+        assert_eq!((aggregate.price, aggregate.num_publishers), (255, 2));
+    }
+
+    #[test]
+    fn test_submit_quote_overwrites_own_slot() {
+        let publishers = vec![Pubkey::new_unique()];
+        let mut account = empty_price_account();
+        
+        account.submit_quote(&publishers, quote(publishers[0], 250, 95, 1_000)).unwrap();
+        account.submit_quote(&publishers, quote(publishers[0], 240, 95, 1_010)).unwrap();
+        
+        let used = account.quotes.iter().filter(|q| q.publisher == publishers[0]).count();
+        assert_eq!(used, 1);
+        assert_eq!(account.quotes[0].price, 240);
+    }
+
+    #[test]
+    fn test_regional_feeds_have_distinct_addresses() {
+        let nairobi = FeedKey::new("MAIZE", "KE:NAIROBI", "WHITE");
+        let kampala = FeedKey::new("MAIZE", "UG:KAMPALA", "WHITE");
+        assert_eq!(&nairobi.commodity[..6], b"MAIZE\0");
+        
+        let (nairobi_price, _) = pda::find_price_account_address(&nairobi);
+        let (kampala_price, _) = pda::find_price_account_address(&kampala);
+        assert_ne!(nairobi_price, kampala_price);
+        assert_ne!(
+            pda::find_feed_config_address(&nairobi_price).0,
+            pda::find_feed_config_address(&kampala_price).0
+        );
+    }
+
+    #[test]
+    fn test_convert_price_through_fx_feed() {
+        let fx_config = FeedConfig {
+            commodity: FeedKey::fx("USD", "KES").commodity,
+            unit: pad_bytes("USD"),
+            currency: pad_bytes("KES"),
+            ..FeedConfig::test(Pubkey::new_unique(), 4)
+        };
+        let usd = pad_bytes("USD");
+        let kes = pad_bytes("KES");
+        
+        // $31.00 per bag at 129.0000 KES/USD is KES 3,999.00
+        assert_eq!(convert_price(3_100, &usd, &kes, 1_290_000, &fx_config).unwrap(), 399_900);
+        assert_eq!(convert_price(399_900, &kes, &usd, 1_290_000, &fx_config).unwrap(), 3_100);
+        assert!(convert_price(3_100, &usd, &pad_bytes("UGX"), 1_290_000, &fx_config).is_err());
+        assert!(convert_price(3_100, &usd, &kes, 0, &fx_config).is_err());
+    }
+
+    #[test]
+    fn test_feed_params_validation() {
+        let mut params = FeedParams {
+            unit: *b"90KG_BAG\0\0\0\0\0\0\0\0",
+            currency: *b"USD\0\0\0\0\0",
+            decimals: 2,
+            heartbeat: 300,
+            max_staleness: 3600,
+            max_deviation_bps: 0,
+            max_hourly_change_bps: 0,
+            commit_period: 0,
+            reveal_period: 0,
+            fixing_window: 0,
+        };
+        assert!(params.validate().is_ok());
+        
+        // Commit-reveal needs both phases
+        params.commit_period = 120;
+        assert!(params.validate().is_err());
+        params.reveal_period = 60;
+        assert!(params.validate().is_ok());
+        
+        params.max_staleness = 60;
+        assert!(params.validate().is_err());
+        
+        params.heartbeat = 0;
+        assert!(params.validate().is_err());
+    }
+
+    #[test]
+    fn test_circuit_breaker_limits() {
+        let mut feed_config = FeedConfig {
+            max_deviation_bps: 1_000,
+            ..FeedConfig::test(Pubkey::default(), 2)
+        };
+        let aggregate = |price| Aggregate {
+            price,
+            confidence: 100,
+            num_publishers: 3,
+        };
+        let mut account = empty_price_account();
+        
+        // First price is never a deviation
+        assert_eq!(account.breaches_limits(&aggregate(10_000), &feed_config), None);
+        account.apply(&aggregate(10_000), 1_000);
+        assert_eq!(account.reference_price, 10_000);
+        
+        assert_eq!(account.breaches_limits(&aggregate(10_900), &feed_config), None);
+        assert_eq!(account.breaches_limits(&aggregate(8_500), &feed_config), Some(1_500));
+        
+        // Three 8% steps within the hour add up past the 20% hourly limit
+        feed_config.max_hourly_change_bps = 2_000;
+        account.apply(&aggregate(10_800), 1_300);
+        account.apply(&aggregate(11_600), 1_600);
+        assert_eq!(account.breaches_limits(&aggregate(12_500), &feed_config), Some(2_500));
+        
+        // A new period measures from the price accepted at its start
+        account.apply(&aggregate(11_600), 1_000 + RATE_OF_CHANGE_PERIOD);
+        assert_eq!(account.reference_price, 11_600);
+        assert_eq!(account.breaches_limits(&aggregate(12_500), &feed_config), None);
+    }
+
+    fn signature_instruction_data(pubkey: &Pubkey, message: &[u8], instruction_index: u16) -> Vec<u8> {
+        // Offsets as laid out by the ed25519 precompile's own instruction builder
+        let pubkey_offset: u16 = 16;
+        let signature_offset = pubkey_offset + 32;
+        let message_offset = signature_offset + 64;
+        let mut data = vec![1u8, 0];
+        for value in [
+            signature_offset,
+            instruction_index,
+            pubkey_offset,
+            instruction_index,
+            message_offset,
+            message.len() as u16,
+            instruction_index,
+        ] {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        data.extend_from_slice(pubkey.as_ref());
+        data.extend_from_slice(&[0u8; 64]);
+        data.extend_from_slice(message);
+        data
+    }
+
+    #[test]
+    fn test_parse_signature_instruction() {
+        let publisher = Pubkey::new_unique();
+        let report = PriceReport {
+            price_account: Pubkey::new_unique(),
+            price: 3_100,
+            confidence: 95,
+            timestamp: 1_700_000_000,
+        };
+        let message = report.message();
+        
+        let data = signature_instruction_data(&publisher, &message, u16::MAX);
+        let (signer, signed) = ed25519::parse_signature_instruction(&data).unwrap();
+        assert_eq!(signer, publisher);
+        assert_eq!(signed, &message[..]);
+        
+        // Signatures over data in another instruction are rejected
+        let data = signature_instruction_data(&publisher, &message, 0);
+        assert!(ed25519::parse_signature_instruction(&data).is_err());
+        assert!(ed25519::parse_signature_instruction(&data[..10]).is_err());
+    }
+
+    #[test]
+    fn test_slash_takes_unbonding_first() {
+        let mut account = PublisherAccount {
+            publisher: Pubkey::new_unique(),
+            bonded: 1_000,
+            unbonding: 300,
+            unbond_requested_at: 0,
+            last_slashed_report: 0,
+            ..Default::default()
+        };
+        
+        assert_eq!(account.slash(500), 500);
+        assert_eq!((account.unbonding, account.bonded), (0, 800));
+        
+        // Capped at what is left
+        assert_eq!(account.slash(5_000), 800);
+        assert_eq!(account.bonded, 0);
+        assert_eq!(account.slash(1), 0);
+    }
+
+    #[test]
+    fn test_reveal_must_match_commitment() {
+        let honest = Pubkey::new_unique();
+        let copier = Pubkey::new_unique();
+        let salt = [7u8; 32];
+        let feed_config = FeedConfig {
+            commit_period: 60,
+            reveal_period: 30,
+            ..FeedConfig::test(Pubkey::default(), 2)
+        };
+        let mut round = PriceRound {
+            price_account: Pubkey::default(),
+            round_id: 0,
+            commit_end: 0,
+            reveal_end: 0,
+            entries: [RoundCommitment::default(); MAX_PUBLISHERS],
+            num_entries: 0,
+            finalized: true,
+            bump: 0,
+        };
+        round.open(Pubkey::new_unique(), &feed_config, 1_000);
+        assert_eq!((round.round_id, round.commit_end, round.reveal_end), (1, 1_060, 1_090));
+        
+        let commitment = commitment_hash(&round.price_account, 1, &honest, 3_100, 95, &salt);
+        round.commit(honest, commitment).unwrap();
+        // Mirroring another publisher's commitment doesn't let the copier reveal its quote
+        round.commit(copier, commitment).unwrap();
+        assert_eq!(round.commitments().len(), 2);
+        
+        assert!(round.reveal(honest, 3_200, 95, &salt).is_err());
+        assert!(round.reveal(honest, 3_100, 95, &[0u8; 32]).is_err());
+        assert!(round.reveal(copier, 3_100, 95, &salt).is_err());
+        assert!(round.reveal(Pubkey::new_unique(), 3_100, 95, &salt).is_err());
+        round.reveal(honest, 3_100, 95, &salt).unwrap();
+        assert!(round.reveal(honest, 3_100, 95, &salt).is_err());
+        
+        let revealed: Vec<bool> = round.commitments().iter().map(|c| c.revealed).collect();
+        assert_eq!(revealed, vec![true, false]);
+        assert_eq!(round.commitments()[0].price, 3_100);
+        
+        // A new round clears the commitments and changes the hash
+        round.open(round.price_account, &feed_config, 1_100);
+        assert!(round.commitments().is_empty());
+        assert_ne!(
+            commitment_hash(&round.price_account, 2, &honest, 3_100, 95, &salt),
+            commitment
+        );
+    }
+
+    #[test]
+    fn test_decode_external_sources() {
+        let pyth_data = include_bytes!("../tests/fixtures/pyth_price_update_v2.bin");
+        let pyth = external::decode_pyth_price_update(pyth_data).unwrap();
+        assert_eq!((pyth.price, pyth.exponent), (245_310_000, -8));
+        // 24 cent spread on 245 is 9%
+        assert_eq!(
+            pyth.normalize(2).unwrap(),
+            external::SourcePrice { price: 245, confidence: 91, timestamp: 1_717_000_000 }
+        );
+        assert_eq!(pyth.normalize(4).unwrap().price, 24_531);
+        
+        let switchboard_data = include_bytes!("../tests/fixtures/switchboard_pull_feed.bin");
+        let switchboard = external::decode_switchboard_pull_feed(switchboard_data).unwrap();
+        assert_eq!(switchboard.num_samples, 5);
+        assert_eq!(
+            switchboard.normalize(2).unwrap(),
+            external::SourcePrice { price: 247, confidence: 99, timestamp: 1_717_000_030 }
+        );
+        assert_eq!(deviation_bps(245, 247), 81);
+        
+        // Partially verified updates, other account types and truncated data are rejected
+        let mut partial = pyth_data.to_vec();
+        partial[40] = 0;
+        assert!(external::decode_pyth_price_update(&partial).is_err());
+        assert!(external::decode_pyth_price_update(switchboard_data).is_err());
+        assert!(external::decode_switchboard_pull_feed(&switchboard_data[..2_000]).is_err());
+    }
+
+    #[test]
+    fn test_rewards_go_to_points_unclaimed_at_each_deposit() {
+        let mut pool = RewardPool::default();
+        let mut early = PublisherAccount::default();
+        let mut late = PublisherAccount::default();
+        
+        // Held until there are points to share it
+        pool.distribute(400).unwrap();
+        early.credit_point(&mut pool).unwrap();
+        early.credit_point(&mut pool).unwrap();
+        pool.distribute(600).unwrap();
+        
+        // Points earned after a deposit don't share it
+        late.credit_point(&mut pool).unwrap();
+        late.credit_point(&mut pool).unwrap();
+        assert!(late.clone().claim_rewards(&mut pool.clone()).is_err());
+        pool.distribute(600).unwrap();
+        early.credit_point(&mut pool).unwrap();
+        
+        // Claim order doesn't matter
+        let (mut pool_b, mut early_b, mut late_b) = (pool.clone(), early.clone(), late.clone());
+        assert_eq!(early.claim_rewards(&mut pool).unwrap(), 1_300);
+        assert_eq!(late.claim_rewards(&mut pool).unwrap(), 300);
+        assert_eq!(late_b.claim_rewards(&mut pool_b).unwrap(), 300);
+        assert_eq!(early_b.claim_rewards(&mut pool_b).unwrap(), 1_300);
+        
+        // Claiming retires every unclaimed point, including ones credited after the last deposit
+        assert_eq!((early.reward_points, late.reward_points, pool.total_points), (0, 0, 0));
+        assert!(early.claim_rewards(&mut pool).is_err());
+        pool.distribute(90).unwrap();
+        assert_eq!(pool.undistributed, 90);
+        assert_eq!(early.rewards_claimed, 1_300);
+    }
+
+    #[test]
+    fn test_proposal_timelock_starts_at_threshold() {
+        let mut proposal = Proposal {
+            id: 0,
+            proposer: Pubkey::new_unique(),
+            config_version: 0,
+            instruction: ProposalInstruction {
+                program_id: crate::ID,
+                accounts: vec![],
+                data: vec![],
+            },
+            approvals: 0,
+            approved_at: 0,
+            executed: false,
+            bump: 0,
+        };
+        
+        proposal.approve(0, 2, 100);
+        // Approving twice doesn't count twice
+        proposal.approve(0, 2, 150);
+        assert_eq!(proposal.approved_at, 0);
+        
+        proposal.approve(3, 2, 200);
+        assert_eq!(proposal.approved_at, 200);
+        proposal.approve(1, 2, 300);
+        assert_eq!(proposal.approved_at, 200);
+    }
+
+    #[test]
+    fn test_governance_config_validation() {
+        let members: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+        let mut governance = Governance {
+            members: vec![],
+            threshold: 0,
+            timelock: 0,
+            proposal_count: 0,
+            config_version: 0,
+            bump: 0,
+        };
+        
+        assert!(governance.set_config(&members, 2, 86_400).is_ok());
+        assert_eq!(governance.member_index(&members[2]), Some(2));
+        assert!(governance.set_config(&members, 4, 86_400).is_err());
+        assert!(governance.set_config(&[members[0], members[0]], 1, 0).is_err());
+        assert!(governance.set_config(&[], 1, 0).is_err());
+    }
+
+    #[test]
+    fn test_price_data_from_feed() {
+        let mut account = empty_price_account();
+        account.price = 247;
+        account.confidence = 98;
+        account.timestamp = 1_700_000_000;
+        account.num_publishers = 3;
+        account.update_count = 42;
+        let feed_config = FeedConfig {
+            commodity: account.commodity,
+            unit: pad_bytes("LB"),
+            currency: pad_bytes("USD"),
+            ..FeedConfig::test(Pubkey::new_unique(), 2)
+        };
+        
+        let data = PriceData::new(feed_config.price_account, &account, &feed_config);
+        assert_eq!(data.version, PriceData::VERSION);
+        assert_eq!((data.price, data.exponent), (247, -2));
+        assert_eq!(data.publish_time, 1_700_000_000);
+        assert_eq!(data.update_count, 42);
+        assert_eq!(data.status, FeedStatus::Active);
+        
+        // Fits the return data limit with room to grow
+        assert!(PriceData::try_to_vec(&data).unwrap().len() * MAX_PRICE_DATA_READS + 4 <= 1024);
+    }
+
+    #[test]
+    fn test_migrate_legacy_price_account() {
+        // Byte image of an account written by the original single-publisher program
+        let last_updater = Pubkey::new_unique();
+        let mut legacy = PriceAccount::DISCRIMINATOR.to_vec();
+        legacy.extend_from_slice(&pad_bytes::<32>("COFFEE"));
+        legacy.extend_from_slice(&247u64.to_le_bytes());
+        legacy.push(95);
+        legacy.extend_from_slice(&1_700_000_000i64.to_le_bytes());
+        legacy.extend_from_slice(last_updater.as_ref());
+        legacy.extend_from_slice(&9u64.to_le_bytes());
+        legacy.push(254);
+        assert_eq!(legacy.len(), 8 + 90);
+        
+        let read = migration::LegacyPriceAccount::read(&legacy).unwrap();
+        assert_eq!((read.price, read.confidence, read.update_count, read.bump), (247, 95, 9, 254));
+        assert!(migration::LegacyPriceAccount::read(&legacy[..legacy.len() - 1]).is_err());
+        assert!(migration::upgrade_price_account(&legacy).is_err());
+        
+        // It moves into the global feed of its commodity, if that hasn't priced yet
+        let mut regional = empty_price_account();
+        regional.commodity = pad_bytes("COFFEE");
+        regional.location = pad_bytes("KE:NAIROBI");
+        assert!(read.move_into(&mut regional).is_err());
+        
+        let mut global = empty_price_account();
+        global.commodity = pad_bytes("COFFEE");
+        global.update_count = 1;
+        assert!(read.move_into(&mut global).is_err());
+        
+        global.update_count = 0;
+        read.move_into(&mut global).unwrap();
+        assert_eq!((global.price, global.confidence, global.timestamp), (247, 95, 1_700_000_000));
+        assert_eq!((global.last_updater, global.update_count), (last_updater, 9));
+        assert_eq!(global.reference_price, 247);
+        
+        // Legacy addresses are keyed by commodity alone
+        let (address, _) = pda::find_legacy_price_account_address(&read.commodity);
+        assert_ne!(address, pda::find_price_account_address(&global.feed_key()).0);
+        
+        let mut current = Vec::new();
+        global.try_serialize(&mut current).unwrap();
+        assert_eq!(current.len(), 8 + PriceAccount::LEN);
+        assert!(migration::upgrade_price_account(&current).is_err());
+    }
+
+    fn observation(price: u64, timestamp: i64) -> PriceObservation {
+        PriceObservation {
+            price,
+            timestamp,
+            confidence: 100,
+            _padding: [0; 7],
+        }
+    }
+
+    #[test]
+    fn test_history_buckets_and_wraps() {
+        let mut history: PriceHistory = bytemuck::Zeroable::zeroed();
+        
+        history.record(observation(100, 1));
+        history.record(observation(110, HISTORY_INTERVAL));
+        assert_eq!(history.len, 1);
+        assert_eq!(history.latest().unwrap().price, 110);
+        
+        for i in 1..=HISTORY_CAPACITY as i64 {
+            history.record(observation(100 + i as u64, i * HISTORY_INTERVAL));
+        }
+        assert_eq!(history.len as usize, HISTORY_CAPACITY);
+        assert_eq!(history.get(0).timestamp, HISTORY_INTERVAL);
+        assert_eq!(history.latest().unwrap().timestamp, HISTORY_CAPACITY as i64 * HISTORY_INTERVAL);
+    }
+
+    #[test]
+    fn test_twap_weights_by_time() {
+        let mut history: PriceHistory = bytemuck::Zeroable::zeroed();
+        assert!(history.twap(1_000, 600, 3_600).is_err());
+        
+        history.record(observation(100, 0));
+        history.record(observation(200, 900));
+        history.record(observation(400, 1_200));
+        
+        // [600, 900) at 100, [900, 1200) at 200
+        assert_eq!(history.twap(1_200, 600, 3_600).unwrap(), 150);
+        // Latest price carries forward to the end of the window
+        assert_eq!(history.twap(1_800, 600, 3_600).unwrap(), 400);
+        // Window ending before later observations ignores them
+        assert_eq!(history.twap(900, 900, 3_600).unwrap(), 100);
+    }
+
+    #[test]
+    fn test_twap_rejects_stale_or_sparse_history() {
+        let mut history: PriceHistory = bytemuck::Zeroable::zeroed();
+        history.record(observation(100, 0));
+        history.record(observation(200, 900));
+        history.record(observation(400, 1_200));
+        
+        // A publisher outage doesn't stretch the last price over the window
+        assert!(history.twap(1_800, 600, 599).is_err());
+        assert_eq!(history.twap(1_800, 600, 600).unwrap(), 400);
+        
+        // Half the window must be covered: [0, 1200) of [-1200, 1200] is, of [-1300, 1200] isn't
+        assert_eq!(history.twap(1_200, 2_400, 3_600).unwrap(), 125);
+        assert!(history.twap(1_200, 2_500, 3_600).is_err());
+    }
+
+    #[test]
+    fn test_realized_volatility() {
+        // ln(2) and ln(1.01)
+        assert!((log_return(100, 200) - 693_147_180_560).abs() < 100_000);
+        assert!((log_return(3_100, 3_131) - 9_950_330_853).abs() < 10);
+        assert_eq!(log_return(3_131, 3_100), -log_return(3_100, 3_131));
+        
+        let mut history: PriceHistory = bytemuck::Zeroable::zeroed();
+        history.record(observation(3_100, 0));
+        history.record(observation(3_105, 300));
+        assert_eq!(history.realized_volatility(VOLATILITY_RETURNS), None);
+        for (i, price) in [3_098, 3_110, 3_102, 3_095, 3_101].into_iter().enumerate() {
+            history.record(observation(price, 600 + 300 * i as i64));
+        }
+
+        // Annualized stdev of the 5 minute log returns: 89.51%, and 81.67% over the last 3
+        let (bps, returns) = history.realized_volatility(VOLATILITY_RETURNS).unwrap();
+        assert_eq!(returns, 6);
+        assert!(bps.abs_diff(8_951) <= 1);
+        let (bps, returns) = history.realized_volatility(3).unwrap();
+        assert_eq!(returns, 3);
+        assert!(bps.abs_diff(8_167) <= 1);
+        
+        let mut account = empty_price_account();
+        account.update_volatility(&history);
+        assert_eq!(account.volatility_returns, 6);
+    }
+
+    fn empty_curve() -> ForwardCurve {
+        ForwardCurve {
+            price_account: Pubkey::default(),
+            num_tenors: 0,
+            tenors: [Tenor::default(); MAX_TENORS],
+            publishers: [Pubkey::default(); MAX_PUBLISHERS],
+            last_updater: Pubkey::default(),
+            bump: 0,
+        }
+    }
+
+    fn curve_update(contract: &str, delivery: i64, price: u64) -> CurveUpdate {
+        CurveUpdate {
+            contract: pad_bytes(contract),
+            delivery,
+            price,
+            confidence: 90,
+        }
+    }
+
+    #[test]
+    fn test_forward_curve_interpolation() {
+        let month = 30 * 24 * 60 * 60;
+        let publishers = [Pubkey::new_unique(), Pubkey::new_unique()];
+        let feed_config = FeedConfig::test(Pubkey::default(), 2);
+        let mut curve = empty_curve();
+        let quote_all = |curve: &mut ForwardCurve, update: CurveUpdate, now: i64| {
+            for publisher in publishers {
+                let slot = curve.publisher_slot(&publishers, publisher).unwrap();
+                curve.quote(slot, &update, now)?;
+            }
+            curve.settle(&publishers, &feed_config, 2, 300, now);
+            Ok::<(), Error>(())
+        };
+        
+        // Inserted out of order, kept sorted by delivery
+        quote_all(&mut curve, curve_update("MAR26", 3 * month, 8_600), 100).unwrap();
+        quote_all(&mut curve, curve_update("DEC25", month, 8_200), 200).unwrap();
+        quote_all(&mut curve, curve_update("MAY26", 5 * month, 8_900), 100).unwrap();
+        quote_all(&mut curve, curve_update("DEC25", month, 8_000), 100).unwrap();
+        let deliveries: Vec<i64> = curve.tenors().iter().map(|t| t.point.delivery).collect();
+        assert_eq!(deliveries, vec![month, 3 * month, 5 * month]);
+        
+        assert_eq!(curve.price_at(month).unwrap().price, 8_000);
+        assert_eq!(curve.price_at(month).unwrap().contract, pad_bytes("DEC25"));
+        // A quarter of the way from DEC25 to MAR26
+        let interpolated = curve.price_at(month + month / 2).unwrap();
+        assert_eq!(interpolated.price, 8_150);
+        assert_eq!(interpolated.contract, [0u8; 8]);
+        assert_eq!(curve.price_at(4 * month).unwrap().price, 8_750);
+        assert!(curve.price_at(0).is_err());
+        assert!(curve.price_at(6 * month).is_err());
+        
+        // Delivered months roll off
+        curve.roll(month + 1);
+        assert_eq!(curve.tenors().len(), 2);
+        assert_eq!(curve.tenors[2], Tenor::default());
+        assert!(curve.price_at(month + month / 2).is_err());
+        
+        for i in 0..MAX_TENORS as i64 - 2 {
+            quote_all(&mut curve, curve_update("", 6 * month + i, 9_000), 100).unwrap();
+        }
+        assert!(quote_all(&mut curve, curve_update("", 20 * month, 9_000), 100).is_err());
+    }
+
+    #[test]
+    fn test_forward_curve_needs_quorum_and_breaker() {
+        let month = 30 * 24 * 60 * 60;
+        let (a, b, c) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let publishers = [a, b, c];
+        let feed_config = FeedConfig {
+            max_deviation_bps: 1_000,
+            ..FeedConfig::test(Pubkey::default(), 2)
+        };
+        let mut curve = empty_curve();
+        let quote = |curve: &mut ForwardCurve, publishers: &[Pubkey], publisher, price, now| {
+            let slot = curve.publisher_slot(publishers, publisher).unwrap();
+            curve.quote(slot, &curve_update("DEC25", month, price), now).unwrap();
+            curve.settle(publishers, &feed_config, 2, 300, now)
+        };
+        
+        // One publisher can't price a month
+        quote(&mut curve, &publishers, a, 8_000, 100);
+        assert!(curve.price_at(month).is_err());
+        quote(&mut curve, &publishers, b, 8_200, 110);
+        assert_eq!(curve.price_at(month).unwrap().price, 8_100);
+        
+        // Nor move it: an outlier only shifts the median
+        quote(&mut curve, &publishers, c, 12_000, 120);
+        assert_eq!(curve.price_at(month).unwrap().price, 8_200);
+        
+        // A quorum moving past the deviation limit halts the month
+        let halted = quote(&mut curve, &publishers, b, 12_000, 130);
+        assert_eq!(halted.len(), 1);
+        assert_eq!((halted[0].last_price, halted[0].rejected_price), (8_200, 12_000));
+        assert!(curve.price_at(month).is_err());
+        
+        // Quotes at the halt don't confirm it; a quorum after it resumes the month
+        quote(&mut curve, &publishers, a, 12_100, 130);
+        quote(&mut curve, &publishers, c, 12_000, 200);
+        assert!(curve.price_at(month).is_err());
+        quote(&mut curve, &publishers, b, 12_100, 210);
+        assert_eq!(curve.price_at(month).unwrap().price, 12_050);
+        
+        // A removed publisher's quotes stop counting and its slot is reused
+        let d = Pubkey::new_unique();
+        let remaining = [a, b, d];
+        let slot_c = curve.publisher_slot(&publishers, c).unwrap();
+        assert_eq!(curve.publisher_slot(&remaining, d).unwrap(), slot_c);
+        assert_eq!(curve.tenors[0].quotes[slot_c], TenorQuote::default());
+    }
+
+    #[test]
+    fn test_fixing_from_spot_or_twap() {
+        let mut history: PriceHistory = bytemuck::Zeroable::zeroed();
+        history.record(observation(100, 0));
+        history.record(observation(200, 900));
+        history.record(observation(400, 1_200));
+        
+        // Spot fixes the aggregate in force, ignoring later prints
+        assert_eq!(history.fixing(900, 0, 3_600).unwrap().price, 200);
+        assert_eq!(history.fixing(1_200, 0, 3_600).unwrap().price, 400);
+        assert_eq!(history.fixing(1_200, 600, 3_600).unwrap().price, 150);
+        
+        // Nothing before the fixing, or nothing recent enough
+        assert!(history.fixing(-300, 0, 3_600).is_err());
+        assert!(history.fixing(5_100, 600, 3_600).is_err());
+        assert_eq!(history.fixing(4_800, 600, 3_600).unwrap().price, 400);
+        
+        // Only bucket boundaries can be fixed
+        assert!(history.fixing(1_100, 0, 3_600).is_err());
+    }
+
+    #[test]
+    fn test_fixing_keeps_print_in_force_across_bucket_updates() {
+        let mut history: PriceHistory = bytemuck::Zeroable::zeroed();
+        history.record(observation(100, 600));
+        history.record(observation(200, 1_100));
+        // Prints at and after the fixing time, in what used to be one bucket
+        history.record(observation(300, 1_200));
+        history.record(observation(500, 1_250));
+        history.record(observation(600, 1_400));
+        
+        // Each bucket keeps the print in force when it closes
+        assert_eq!(history.len, 3);
+        assert_eq!(history.fixing(1_200, 0, 3_600).unwrap().price, 300);
+        assert_eq!(history.fixing(1_500, 0, 3_600).unwrap().price, 600);
+        assert_eq!(history.fixing(900, 0, 3_600).unwrap().price, 100);
+    }
+
+    #[test]
+    fn test_close_feed_requires_no_markets_or_fixings() {
+        let mut price_account = empty_price_account();
+        price_account.lifecycle = FeedLifecycle::Deprecated;
+        price_account.retire_at = 1_000;
+        price_account.open_markets = 1;
+        price_account.open_fixings = 2;
+        
+        assert!(price_account.check_closable(999).is_err());
+        assert!(price_account.check_closable(1_000).is_err());
+        price_account.open_markets = 0;
+        assert!(price_account.check_closable(1_000).is_err());
+        price_account.open_fixings = 0;
+        price_account.check_closable(1_000).unwrap();
+        
+        price_account.lifecycle = FeedLifecycle::Active;
+        assert!(price_account.check_closable(1_000).is_err());
+    }
+
+    #[test]
+    fn test_only_oracle_signers_record_fixings() {
+        let publisher = Pubkey::new_unique();
+        let mut oracle_state = OracleState {
+            authority: Pubkey::new_unique(),
+            market_registry: Pubkey::new_unique(),
+            ..Default::default()
+        };
+        oracle_state.publishers[0] = publisher;
+        oracle_state.publisher_count = 1;
+        
+        assert!(oracle_state.can_record_fixing(&oracle_state.authority));
+        assert!(oracle_state.can_record_fixing(&oracle_state.market_registry));
+        assert!(oracle_state.can_record_fixing(&publisher));
+        assert!(!oracle_state.can_record_fixing(&Pubkey::new_unique()));
+        
+        // A removed publisher's slot past the count no longer counts
+        oracle_state.publisher_count = 0;
+        assert!(!oracle_state.can_record_fixing(&publisher));
+    }
+
+    #[test]
+    fn test_closing_a_feed_keeps_the_index_dense() {
+        let feeds: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+        let entry = |index: u32| FeedIndex { index, price_account: feeds[index as usize], bump: 0 };
+        let mut oracle_state = OracleState { total_commodities: 3, ..Default::default() };
+        
+        // Closing a middle feed moves the last one into its slot
+        let mut slot = entry(1);
+        let moved = oracle_state.remove_feed_index(&mut slot, &entry(2)).unwrap();
+        assert_eq!(moved, Some(feeds[2]));
+        assert_eq!((slot.index, slot.price_account), (1, feeds[2]));
+        assert_eq!(oracle_state.total_commodities, 2);
+        
+        // `last` must be the current last entry
+        assert!(oracle_state.remove_feed_index(&mut entry(0), &entry(2)).is_err());
+        
+        // Closing the last feed just shrinks the index, so the next feed reuses its slot
+        let mut last = entry(1);
+        last.price_account = feeds[2];
+        let moved = oracle_state.remove_feed_index(&mut last.clone(), &last).unwrap();
+        assert_eq!(moved, None);
+        assert_eq!(oracle_state.total_commodities, 1);
+        
+        let only = entry(0);
+        assert_eq!(oracle_state.remove_feed_index(&mut only.clone(), &only).unwrap(), None);
+        assert_eq!(oracle_state.total_commodities, 0);
+        assert!(oracle_state.remove_feed_index(&mut only.clone(), &only).is_err());
+    }
 }
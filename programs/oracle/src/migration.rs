@@ -0,0 +1,79 @@
+//! Layouts of `PriceAccount` before versioning, and their conversion to the current one
+
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+
+use crate::{ErrorCode, PriceAccount};
+
+/// Original single-publisher layout, one feed per commodity at `[b"price", commodity]`.
+/// Those addresses aren't reachable with the current seeds, so a legacy account is
+/// moved into its commodity's global feed (`[b"price", commodity, 0, 0]`) and closed.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct LegacyPriceAccount {
+    pub commodity: [u8; 32],
+    pub price: u64,
+    pub confidence: u8,
+    pub timestamp: i64,
+    pub last_updater: Pubkey,
+    pub update_count: u64,
+    pub bump: u8,
+}
+
+impl LegacyPriceAccount {
+    pub const LEN: usize = 32 + 8 + 1 + 8 + 32 + 8 + 1;
+    
+    /// Legacy prices are in cents
+    pub const DECIMALS: u8 = 2;
+    
+    /// Decode raw account data (discriminator included) in the legacy layout
+    pub fn read(data: &[u8]) -> Result<Self> {
+        let disc = PriceAccount::DISCRIMINATOR;
+        require!(
+            data.len() == disc.len() + Self::LEN && data[..disc.len()] == *disc,
+            ErrorCode::UnknownAccountVersion
+        );
+        Ok(Self::deserialize(&mut &data[disc.len()..])?)
+    }
+
+    /// Carry the legacy price into the global feed of the same commodity. The feed
+    /// must not have priced yet, so the move never overrides an aggregate.
+    pub fn move_into(&self, price_account: &mut PriceAccount) -> Result<()> {
+        require!(
+            price_account.commodity == self.commodity
+                && price_account.location == [0u8; 32]
+                && price_account.grade == [0u8; 32]
+                && price_account.update_count == 0,
+            ErrorCode::LegacyFeedMismatch
+        );
+        
+        price_account.price = self.price;
+        price_account.confidence = self.confidence;
+        price_account.timestamp = self.timestamp;
+        price_account.last_updater = self.last_updater;
+        price_account.update_count = self.update_count;
+        // The circuit breaker measures the first aggregate against the legacy price
+        price_account.reference_price = self.price;
+        price_account.reference_timestamp = self.timestamp;
+        Ok(())
+    }
+}
+
+/// Convert raw `PriceAccount` data (discriminator included) in any versioned layout
+/// to the current one. Versioned layouts store their version right after the
+/// discriminator; legacy accounts are moved with `migrate_legacy_price_account` instead.
+pub fn upgrade_price_account(data: &[u8]) -> Result<PriceAccount> {
+    let disc = PriceAccount::DISCRIMINATOR;
+    require!(
+        data.len() > disc.len() && data[..disc.len()] == *disc,
+        ErrorCode::UnknownAccountVersion
+    );
+    let body = &data[disc.len()..];
+    
+    if body.len() == LegacyPriceAccount::LEN {
+        return err!(ErrorCode::LegacyLayout);
+    }
+    match body[0] {
+        PriceAccount::VERSION => err!(ErrorCode::AlreadyMigrated),
+        _ => err!(ErrorCode::UnknownAccountVersion),
+    }
+}
@@ -0,0 +1,61 @@
+//! PDA derivation helpers for oracle accounts, for clients and consumer programs
+
+use anchor_lang::prelude::*;
+
+use crate::FeedKey;
+
+pub fn find_oracle_state_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"oracle_state"], &crate::ID)
+}
+
+pub fn find_price_account_address(feed_key: &FeedKey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            b"price",
+            feed_key.commodity.as_ref(),
+            feed_key.location.as_ref(),
+            feed_key.grade.as_ref(),
+        ],
+        &crate::ID,
+    )
+}
+
+/// Address of a legacy single-publisher account, see `migration::LegacyPriceAccount`
+pub fn find_legacy_price_account_address(commodity: &[u8; 32]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"price", commodity.as_ref()], &crate::ID)
+}
+
+pub fn find_feed_config_address(price_account: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"feed_config", price_account.as_ref()], &crate::ID)
+}
+
+pub fn find_history_address(price_account: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"history", price_account.as_ref()], &crate::ID)
+}
+
+pub fn find_feed_index_address(index: u32) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"feed_index", index.to_le_bytes().as_ref()], &crate::ID)
+}
+
+pub fn find_fixing_address(price_account: &Pubkey, fixing_time: i64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"fixing", price_account.as_ref(), fixing_time.to_le_bytes().as_ref()],
+        &crate::ID,
+    )
+}
+
+pub fn find_feed_sources_address(price_account: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"sources", price_account.as_ref()], &crate::ID)
+}
+
+pub fn find_forward_curve_address(price_account: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"curve", price_account.as_ref()], &crate::ID)
+}
+
+pub fn find_publisher_address(publisher: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"publisher", publisher.as_ref()], &crate::ID)
+}
+
+pub fn find_reward_pool_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"reward_pool"], &crate::ID)
+}
@@ -3,6 +3,15 @@ use anchor_spl::token::{self, Token, TokenAccount, Transfer};
 
 declare_id!("mkt1exxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxx");
 
+/// Default share of the pool (bps) paid to the oracle fee vault at resolution
+pub const RESOLUTION_FEE_BPS: u16 = 50;
+
+/// Upper bound for `MarketState.resolution_fee_bps`
+pub const MAX_RESOLUTION_FEE_BPS: u16 = 500;
+
+/// Seconds after expiry before a market with no fixing can be voided (7 days)
+pub const VOID_GRACE_PERIOD: i64 = 7 * 24 * 60 * 60;
+
 #[program]
 pub mod prediction_market {
     use super::*;
@@ -13,26 +22,95 @@ pub mod prediction_market {
         market_state.authority = ctx.accounts.authority.key();
         market_state.total_markets = 0;
         market_state.bump = ctx.bumps.market_state;
+        market_state.pending_authority = Pubkey::default();
+        market_state.resolution_fee_bps = RESOLUTION_FEE_BPS;
         
         msg!("Market state initialized");
         Ok(())
     }
 
-    /// Create a new prediction market
+    /// Create a new prediction market on a specific (regional) oracle feed.
+    /// `threshold_price` is in `threshold_currency` with the feed's decimals; when that
+    /// differs from the feed currency, pass the FX feed used to convert at settlement.
+    /// `expiry_time` must be a multiple of `oracle::HISTORY_INTERVAL`, as fixings are.
     pub fn create_market(
         ctx: Context<CreateMarket>,
         market_id: u64,
-        commodity: [u8; 32],
         threshold_price: u64,
+        threshold_currency: [u8; 8],
         expiry_time: i64,
     ) -> Result<()> {
         let clock = Clock::get()?;
         require!(expiry_time > clock.unix_timestamp, ErrorCode::InvalidExpiryTime);
+        // The oracle only fixes prices on its history bucket boundaries
+        require!(
+            expiry_time % oracle::HISTORY_INTERVAL == 0,
+            ErrorCode::UnalignedExpiryTime
+        );
+        
+        // Only canonical feed PDAs, so a market can't be created on a look-alike account
+        oracle_consumer::CheckedPriceAccount::load(&ctx.accounts.price_oracle.to_account_info())?;
+        require!(
+            ctx.accounts.price_oracle.lifecycle == oracle::FeedLifecycle::Active,
+            ErrorCode::OracleFeedDeprecated
+        );
         
+        let feed_currency = ctx.accounts.feed_config.currency;
+        let fx_feed = if threshold_currency == feed_currency {
+            Pubkey::default()
+        } else {
+            let (Some(fx_feed_config), Some(fx_price_oracle)) = (
+                ctx.accounts.fx_feed_config.as_ref(),
+                ctx.accounts.fx_price_oracle.as_ref(),
+            ) else {
+                return err!(ErrorCode::MissingFxFeed);
+            };
+            require!(
+                oracle::fx_pairs(fx_feed_config, &feed_currency, &threshold_currency),
+                ErrorCode::WrongFxFeed
+            );
+            require_keys_eq!(
+                fx_price_oracle.key(),
+                fx_feed_config.price_account,
+                ErrorCode::WrongFxFeed
+            );
+            oracle_consumer::CheckedPriceAccount::load(&fx_price_oracle.to_account_info())?;
+            require!(
+                fx_price_oracle.lifecycle == oracle::FeedLifecycle::Active,
+                ErrorCode::OracleFeedDeprecated
+            );
+            fx_feed_config.price_account
+        };
+        
+        // Keep the feeds open until this market resolves
+        let accounts = &ctx.accounts;
+        update_feed_reference(
+            &accounts.oracle_program,
+            &accounts.price_oracle,
+            &accounts.oracle_state,
+            &accounts.market_state,
+            true,
+        )?;
+        if let Some(fx_price_oracle) = accounts.fx_price_oracle.as_ref() {
+            if fx_feed != Pubkey::default() {
+                update_feed_reference(
+                    &accounts.oracle_program,
+                    fx_price_oracle,
+                    &accounts.oracle_state,
+                    &accounts.market_state,
+                    true,
+                )?;
+            }
+        }
+
+        let commodity = ctx.accounts.price_oracle.commodity;
         let market = &mut ctx.accounts.market;
         market.market_id = market_id;
         market.commodity = commodity;
+        market.price_feed = ctx.accounts.price_oracle.key();
         market.threshold_price = threshold_price;
+        market.threshold_currency = threshold_currency;
+        market.fx_feed = fx_feed;
         market.expiry_time = expiry_time;
         market.creation_time = clock.unix_timestamp;
         market.yes_pool = 0;
@@ -43,14 +121,19 @@ pub mod prediction_market {
         market.oracle_price = 0;
         market.authority = ctx.accounts.authority.key();
         market.bump = ctx.bumps.market;
+        market.resolution_fee = 0;
+        market.voided = false;
         
         let market_state = &mut ctx.accounts.market_state;
+        market.resolution_fee_bps = market_state.resolution_fee_bps;
         market_state.total_markets += 1;
         
         emit!(MarketCreated {
             market_id,
             commodity,
+            price_feed: market.price_feed,
             threshold_price,
+            threshold_currency,
             expiry_time,
             creator: ctx.accounts.authority.key(),
         });
@@ -122,14 +205,47 @@ pub mod prediction_market {
         );
         require!(!market.resolved, ErrorCode::AlreadyResolved);
         
-        // Check oracle price is not stale
+        // Never settle while the circuit breaker holds the feed
         require!(
-            clock.unix_timestamp - oracle.timestamp < 3600,
-            ErrorCode::StaleOraclePrice
+            oracle.status == oracle::FeedStatus::Active,
+            ErrorCode::OracleFeedHalted
         );
         
-        // Get oracle data
-        let actual_price = oracle.price; // in cents: 247 = $2.47
+        // Settle on the oracle's frozen fixing at expiry, so neither a late
+        // print nor the time of this call can move the outcome
+        let mut actual_price = ctx.accounts.price_fixing.price; // in cents: 247 = $2.47
+        
+        // Local-currency markets convert at the FX fixing for the same time
+        if market.fx_feed != Pubkey::default() {
+            let (Some(fx_oracle), Some(fx_feed_config), Some(fx_fixing)) = (
+                ctx.accounts.fx_price_oracle.as_ref(),
+                ctx.accounts.fx_feed_config.as_ref(),
+                ctx.accounts.fx_fixing.as_ref(),
+            ) else {
+                return err!(ErrorCode::MissingFxFeed);
+            };
+            require_keys_eq!(fx_oracle.key(), market.fx_feed, ErrorCode::WrongPriceFeed);
+            require_keys_eq!(fx_feed_config.price_account, market.fx_feed, ErrorCode::WrongPriceFeed);
+            require_keys_eq!(fx_fixing.price_account, market.fx_feed, ErrorCode::WrongPriceFeed);
+            require!(
+                fx_fixing.fixing_time == market.expiry_time,
+                ErrorCode::WrongFixing
+            );
+            
+            require!(
+                fx_oracle.status == oracle::FeedStatus::Active,
+                ErrorCode::OracleFeedHalted
+            );
+            
+            let fx_rate = fx_fixing.price;
+            actual_price = oracle::convert_price(
+                actual_price,
+                &ctx.accounts.feed_config.currency,
+                &market.threshold_currency,
+                fx_rate,
+                fx_feed_config,
+            )?;
+        }
         let threshold = market.threshold_price; // 250 = $2.50
         
         // Determine outcome: did price reach or exceed threshold?
@@ -141,6 +257,57 @@ pub mod prediction_market {
         market.resolution_time = clock.unix_timestamp;
         market.oracle_price = actual_price;
         
+        // Resolved markets no longer hold their feeds open
+        update_feed_reference(
+            &ctx.accounts.oracle_program,
+            &ctx.accounts.price_oracle,
+            &ctx.accounts.oracle_state,
+            &ctx.accounts.market_state,
+            false,
+        )?;
+        if market.fx_feed != Pubkey::default() {
+            if let Some(fx_price_oracle) = ctx.accounts.fx_price_oracle.as_ref() {
+                update_feed_reference(
+                    &ctx.accounts.oracle_program,
+                    fx_price_oracle,
+                    &ctx.accounts.oracle_state,
+                    &ctx.accounts.market_state,
+                    false,
+                )?;
+            }
+        }
+
+        // Pay the oracle's publishers for the prices this market settled on
+        let total_pool = market.yes_pool + market.no_pool;
+        let fee = (total_pool as u128 * market.resolution_fee_bps as u128 / 10_000) as u64;
+        if fee > 0 {
+            let (Some(oracle_reward_pool), Some(oracle_fee_vault)) = (
+                ctx.accounts.oracle_reward_pool.as_ref(),
+                ctx.accounts.oracle_fee_vault.as_ref(),
+            ) else {
+                return err!(ErrorCode::MissingOracleFeeAccounts);
+            };
+            
+            let seeds = &[
+                b"market".as_ref(),
+                &market.market_id.to_le_bytes(),
+                &[market.bump],
+            ];
+            let signer = &[&seeds[..]];
+            
+            let cpi_accounts = oracle::cpi::accounts::DepositFees {
+                reward_pool: oracle_reward_pool.to_account_info(),
+                fee_vault: oracle_fee_vault.to_account_info(),
+                depositor_token_account: ctx.accounts.market_vault.to_account_info(),
+                depositor: market.to_account_info(),
+                token_program: ctx.accounts.token_program.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.oracle_program.to_account_info();
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+            oracle::cpi::deposit_fees(cpi_ctx, fee)?;
+            market.resolution_fee = fee;
+        }
+
         emit!(MarketResolved {
             market_id: market.market_id,
             outcome,
@@ -159,6 +326,94 @@ pub mod prediction_market {
         Ok(())
     }
 
+    /// Void a market whose expiry fixing was never written, so every position
+    /// can take back its stake (authority only, after `VOID_GRACE_PERIOD`)
+    pub fn void_market(ctx: Context<VoidMarket>) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        let clock = Clock::get()?;
+        
+        // Only markets that can't settle: the feed (or FX) fixing must still be missing
+        let mut fixing_missing = ctx.accounts.price_fixing.data_is_empty();
+        if market.fx_feed != Pubkey::default() {
+            let (Some(fx_oracle), Some(fx_fixing)) = (
+                ctx.accounts.fx_price_oracle.as_ref(),
+                ctx.accounts.fx_fixing.as_ref(),
+            ) else {
+                return err!(ErrorCode::MissingFxFeed);
+            };
+            require_keys_eq!(fx_oracle.key(), market.fx_feed, ErrorCode::WrongPriceFeed);
+            let (fx_fixing_address, _) = oracle::pda::find_fixing_address(&market.fx_feed, market.expiry_time);
+            require_keys_eq!(fx_fixing.key(), fx_fixing_address, ErrorCode::WrongFixing);
+            fixing_missing |= fx_fixing.data_is_empty();
+        }
+        require!(fixing_missing, ErrorCode::FixingAvailable);
+        
+        market.void(clock.unix_timestamp)?;
+        
+        update_feed_reference(
+            &ctx.accounts.oracle_program,
+            &ctx.accounts.price_oracle,
+            &ctx.accounts.oracle_state,
+            &ctx.accounts.market_state,
+            false,
+        )?;
+        if let Some(fx_price_oracle) = ctx.accounts.fx_price_oracle.as_ref() {
+            if market.fx_feed != Pubkey::default() {
+                update_feed_reference(
+                    &ctx.accounts.oracle_program,
+                    fx_price_oracle,
+                    &ctx.accounts.oracle_state,
+                    &ctx.accounts.market_state,
+                    false,
+                )?;
+            }
+        }
+        
+        emit!(MarketVoided {
+            market_id: market.market_id,
+            timestamp: market.resolution_time,
+        });
+        
+        msg!("Market voided: id={}, positions are refunded", market.market_id);
+        Ok(())
+    }
+
+    /// Set the resolution fee for markets created from now on (authority only)
+    pub fn set_resolution_fee(ctx: Context<UpdateMarketConfig>, resolution_fee_bps: u16) -> Result<()> {
+        require!(
+            resolution_fee_bps <= MAX_RESOLUTION_FEE_BPS,
+            ErrorCode::InvalidResolutionFee
+        );
+        ctx.accounts.market_state.resolution_fee_bps = resolution_fee_bps;
+        
+        msg!("Resolution fee set: {} bps", resolution_fee_bps);
+        Ok(())
+    }
+
+    /// Nominate a new authority, which must accept before it takes over (authority only)
+    pub fn propose_authority(ctx: Context<UpdateMarketConfig>, new_authority: Pubkey) -> Result<()> {
+        ctx.accounts.market_state.pending_authority = new_authority;
+        
+        msg!("Authority transfer proposed to {}", new_authority);
+        Ok(())
+    }
+
+    /// Complete an authority transfer (pending authority only)
+    pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+        let market_state = &mut ctx.accounts.market_state;
+        let previous = market_state.authority;
+        market_state.authority = market_state.pending_authority;
+        market_state.pending_authority = Pubkey::default();
+        
+        emit!(AuthorityTransferred {
+            previous,
+            new_authority: market_state.authority,
+        });
+        
+        msg!("Authority transferred to {}", market_state.authority);
+        Ok(())
+    }
+
     /// Claim winnings after market resolution
     pub fn claim_winnings(
         ctx: Context<ClaimWinnings>,
@@ -169,37 +424,14 @@ pub mod prediction_market {
         require!(market.resolved, ErrorCode::MarketNotResolved);
         require!(!position.claimed, ErrorCode::AlreadyClaimed);
         
-        let total_pool = market.yes_pool + market.no_pool;
-        let winning_pool = if market.outcome {
-            market.yes_pool
-        } else {
-            market.no_pool
-        };
-        
-        let user_shares = if market.outcome {
-            position.yes_shares
-        } else {
-            position.no_shares
-        };
-        
-        require!(user_shares > 0, ErrorCode::NoWinningShares);
-        require!(winning_pool > 0, ErrorCode::InvalidPool);
-        
-        // Calculate payout: (user_shares / winning_pool) * total_pool
-        let payout = (user_shares as u128)
-            .checked_mul(total_pool as u128)
-            .unwrap()
-            .checked_div(winning_pool as u128)
-            .unwrap() as u64;
-        
-        require!(payout > 0, ErrorCode::InvalidPayout);
+        let payout = market.payout(position)?;
         
         // Mark as claimed
         position.claimed = true;
         
         // Transfer winnings from vault to user
         let seeds = &[
-            b"market",
+            b"market".as_ref(),
             &market.market_id.to_le_bytes(),
             &[market.bump],
         ];
@@ -225,6 +457,30 @@ pub mod prediction_market {
     }
 }
 
+/// Count (`retain`) or release a market on an oracle feed, signing as the market state PDA
+fn update_feed_reference<'info>(
+    oracle_program: &Program<'info, oracle::program::Oracle>,
+    price_account: &Account<'info, oracle::PriceAccount>,
+    oracle_state: &Account<'info, oracle::OracleState>,
+    market_state: &Account<'info, MarketState>,
+    retain: bool,
+) -> Result<()> {
+    let seeds = &[b"market_state".as_ref(), &[market_state.bump]];
+    let signer = &[&seeds[..]];
+    
+    let cpi_accounts = oracle::cpi::accounts::UpdateFeedReferences {
+        price_account: price_account.to_account_info(),
+        oracle_state: oracle_state.to_account_info(),
+        market_registry: market_state.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(oracle_program.to_account_info(), cpi_accounts, signer);
+    if retain {
+        oracle::cpi::retain_feed(cpi_ctx)
+    } else {
+        oracle::cpi::release_feed(cpi_ctx)
+    }
+}
+
 #[derive(Accounts)]
 pub struct Initialize<'info> {
     #[account(
@@ -242,6 +498,32 @@ pub struct Initialize<'info> {
     pub system_program: Program<'info, System>,
 }
 
+#[derive(Accounts)]
+pub struct UpdateMarketConfig<'info> {
+    #[account(
+        mut,
+        seeds = [b"market_state"],
+        bump = market_state.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub market_state: Account<'info, MarketState>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [b"market_state"],
+        bump = market_state.bump,
+        constraint = market_state.pending_authority == pending_authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub market_state: Account<'info, MarketState>,
+    
+    pub pending_authority: Signer<'info>,
+}
+
 #[derive(Accounts)]
 #[instruction(market_id: u64)]
 pub struct CreateMarket<'info> {
@@ -249,7 +531,7 @@ pub struct CreateMarket<'info> {
         init,
         payer = authority,
         space = 8 + PredictionMarket::LEN,
-        seeds = [b"market", &market_id.to_le_bytes()],
+        seeds = [b"market".as_ref(), &market_id.to_le_bytes()],
         bump
     )]
     pub market: Account<'info, PredictionMarket>,
@@ -261,6 +543,28 @@ pub struct CreateMarket<'info> {
     )]
     pub market_state: Account<'info, MarketState>,
     
+    /// Oracle feed the market settles against
+    #[account(mut)]
+    pub price_oracle: Account<'info, oracle::PriceAccount>,
+    
+    #[account(constraint = feed_config.price_account == price_oracle.key() @ ErrorCode::WrongPriceFeed)]
+    pub feed_config: Account<'info, oracle::FeedConfig>,
+    
+    /// FX feed config and feed, required when the threshold is not in the feed currency
+    pub fx_feed_config: Option<Account<'info, oracle::FeedConfig>>,
+    
+    #[account(mut)]
+    pub fx_price_oracle: Option<Account<'info, oracle::PriceAccount>>,
+    
+    #[account(
+        seeds = [b"oracle_state"],
+        bump = oracle_state.bump,
+        seeds::program = oracle_program.key()
+    )]
+    pub oracle_state: Account<'info, oracle::OracleState>,
+    
+    pub oracle_program: Program<'info, oracle::program::Oracle>,
+    
     #[account(mut)]
     pub authority: Signer<'info>,
     
@@ -303,16 +607,112 @@ pub struct ResolveMarket<'info> {
     )]
     pub market: Account<'info, PredictionMarket>,
     
-    /// CHECK: Oracle price account from oracle program
+    #[account(mut, address = market.price_feed @ ErrorCode::WrongPriceFeed)]
+    pub price_oracle: Account<'info, oracle::PriceAccount>,
+    
+    #[account(
+        seeds = [b"feed_config", price_oracle.key().as_ref()],
+        bump = feed_config.bump,
+        seeds::program = oracle_program.key()
+    )]
+    pub feed_config: Account<'info, oracle::FeedConfig>,
+    
+    /// Oracle fixing of the feed at the market's expiry
+    #[account(
+        seeds = [b"fixing", price_oracle.key().as_ref(), &market.expiry_time.to_le_bytes()],
+        bump = price_fixing.bump,
+        seeds::program = oracle_program.key()
+    )]
+    pub price_fixing: Account<'info, oracle::Fixing>,
+    
     #[account(
-        seeds = [b"price", market.commodity.as_ref()],
+        seeds = [b"oracle_state"],
+        bump = oracle_state.bump,
+        seeds::program = oracle_program.key()
+    )]
+    pub oracle_state: Account<'info, oracle::OracleState>,
+    
+    /// FX feed accounts, required for markets with a local-currency threshold
+    #[account(mut)]
+    pub fx_price_oracle: Option<Account<'info, oracle::PriceAccount>>,
+    
+    pub fx_feed_config: Option<Account<'info, oracle::FeedConfig>>,
+    
+    pub fx_fixing: Option<Account<'info, oracle::Fixing>>,
+    
+    #[account(mut, constraint = market_vault.owner == market.key() @ ErrorCode::InvalidVault)]
+    pub market_vault: Account<'info, TokenAccount>,
+    
+    /// Oracle fee accounts, required when the market charges a resolution fee
+    #[account(
+        mut,
+        seeds = [b"reward_pool"],
+        bump = oracle_reward_pool.bump,
+        seeds::program = oracle_program.key()
+    )]
+    pub oracle_reward_pool: Option<Account<'info, oracle::RewardPool>>,
+    
+    #[account(
+        mut,
+        seeds = [b"fee_vault"],
         bump,
         seeds::program = oracle_program.key()
     )]
-    pub price_oracle: AccountInfo<'info>,
+    pub oracle_fee_vault: Option<Account<'info, TokenAccount>>,
+    
+    /// Signs the oracle's open-market bookkeeping
+    #[account(seeds = [b"market_state"], bump = market_state.bump)]
+    pub market_state: Account<'info, MarketState>,
     
-    /// CHECK: Oracle program
-    pub oracle_program: AccountInfo<'info>,
+    pub oracle_program: Program<'info, oracle::program::Oracle>,
+    
+    pub authority: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct VoidMarket<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", &market.market_id.to_le_bytes()],
+        bump = market.bump
+    )]
+    pub market: Account<'info, PredictionMarket>,
+    
+    #[account(mut, address = market.price_feed @ ErrorCode::WrongPriceFeed)]
+    pub price_oracle: Account<'info, oracle::PriceAccount>,
+    
+    /// CHECK: Fixing PDA of the feed at the market's expiry; only read to confirm it was never written
+    #[account(
+        seeds = [b"fixing", price_oracle.key().as_ref(), &market.expiry_time.to_le_bytes()],
+        bump,
+        seeds::program = oracle_program.key()
+    )]
+    pub price_fixing: UncheckedAccount<'info>,
+    
+    /// FX feed and its fixing PDA, required for markets with a local-currency threshold
+    #[account(mut)]
+    pub fx_price_oracle: Option<Account<'info, oracle::PriceAccount>>,
+    
+    /// CHECK: Address checked against the FX feed's fixing PDA in the handler
+    pub fx_fixing: Option<UncheckedAccount<'info>>,
+    
+    #[account(
+        seeds = [b"oracle_state"],
+        bump = oracle_state.bump,
+        seeds::program = oracle_program.key()
+    )]
+    pub oracle_state: Account<'info, oracle::OracleState>,
+    
+    #[account(
+        seeds = [b"market_state"],
+        bump = market_state.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub market_state: Account<'info, MarketState>,
+    
+    pub oracle_program: Program<'info, oracle::program::Oracle>,
     
     pub authority: Signer<'info>,
 }
@@ -349,17 +749,23 @@ pub struct MarketState {
     pub authority: Pubkey,          // 32
     pub total_markets: u64,         // 8
     pub bump: u8,                   // 1
+    pub pending_authority: Pubkey,  // 32 - Nominated by propose_authority, default if none
+    pub resolution_fee_bps: u16,    // 2 - Copied into markets at creation
 }
 
 impl MarketState {
-    pub const LEN: usize = 32 + 8 + 1;
+    pub const LEN: usize = 32 + 8 + 1 + 32 + 2;
 }
 
 #[account]
+#[derive(Default)]
 pub struct PredictionMarket {
     pub market_id: u64,             // 8
     pub commodity: [u8; 32],        // 32
-    pub threshold_price: u64,       // 8 - Target price in cents
+    pub price_feed: Pubkey,         // 32 - Oracle PriceAccount for commodity + location + grade
+    pub threshold_price: u64,       // 8 - Target price in cents of threshold_currency
+    pub threshold_currency: [u8; 8], // 8 - "USD", "KES"
+    pub fx_feed: Pubkey,            // 32 - FX PriceAccount, default if none needed
     pub expiry_time: i64,           // 8
     pub creation_time: i64,         // 8
     pub yes_pool: u64,              // 8
@@ -370,13 +776,67 @@ pub struct PredictionMarket {
     pub oracle_price: u64,          // 8 - Actual price at resolution
     pub authority: Pubkey,          // 32
     pub bump: u8,                   // 1
+    pub resolution_fee: u64,        // 8 - Paid to the oracle fee vault, excluded from payouts
+    pub resolution_fee_bps: u16,    // 2
+    pub voided: bool,               // 1 - Closed without a fixing; positions get their stake back
 }
 
 impl PredictionMarket {
-    pub const LEN: usize = 8 + 32 + 8 + 8 + 8 + 8 + 8 + 1 + 1 + 8 + 8 + 32 + 1;
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 8 + 32 + 8 + 8 + 8 + 8 + 1 + 1 + 8 + 8 + 32 + 1 + 8 + 2 + 1;
+    
+    /// Close the market without an outcome once `VOID_GRACE_PERIOD` has passed since expiry
+    pub fn void(&mut self, now: i64) -> Result<()> {
+        require!(!self.resolved, ErrorCode::AlreadyResolved);
+        require!(
+            now >= self.expiry_time.saturating_add(VOID_GRACE_PERIOD),
+            ErrorCode::VoidTooEarly
+        );
+        
+        self.resolved = true;
+        self.voided = true;
+        self.resolution_time = now;
+        Ok(())
+    }
+    
+    /// What `position` is owed: its whole stake if voided, otherwise its share
+    /// of the pool (net of the resolution fee) on the winning side
+    pub fn payout(&self, position: &UserPosition) -> Result<u64> {
+        if self.voided {
+            let stake = position.yes_shares + position.no_shares;
+            require!(stake > 0, ErrorCode::InvalidPayout);
+            return Ok(stake);
+        }
+        
+        let total_pool = self.yes_pool + self.no_pool - self.resolution_fee;
+        let winning_pool = if self.outcome {
+            self.yes_pool
+        } else {
+            self.no_pool
+        };
+        
+        let user_shares = if self.outcome {
+            position.yes_shares
+        } else {
+            position.no_shares
+        };
+        
+        require!(user_shares > 0, ErrorCode::NoWinningShares);
+        require!(winning_pool > 0, ErrorCode::InvalidPool);
+        
+        // Calculate payout: (user_shares / winning_pool) * total_pool
+        let payout = (user_shares as u128)
+            .checked_mul(total_pool as u128)
+            .unwrap()
+            .checked_div(winning_pool as u128)
+            .unwrap() as u64;
+        
+        require!(payout > 0, ErrorCode::InvalidPayout);
+        Ok(payout)
+    }
 }
 
 #[account]
+#[derive(Default)]
 pub struct UserPosition {
     pub user: Pubkey,               // 32
     pub market_id: u64,             // 8
@@ -394,7 +854,9 @@ impl UserPosition {
 pub struct MarketCreated {
     pub market_id: u64,
     pub commodity: [u8; 32],
+    pub price_feed: Pubkey,
     pub threshold_price: u64,
+    pub threshold_currency: [u8; 8],
     pub expiry_time: i64,
     pub creator: Pubkey,
 }
@@ -416,6 +878,12 @@ pub struct MarketResolved {
     pub timestamp: i64,
 }
 
+#[event]
+pub struct MarketVoided {
+    pub market_id: u64,
+    pub timestamp: i64,
+}
+
 #[event]
 pub struct WinningsClaimed {
     pub market_id: u64,
@@ -423,6 +891,12 @@ pub struct WinningsClaimed {
     pub payout: u64,
 }
 
+#[event]
+pub struct AuthorityTransferred {
+    pub previous: Pubkey,
+    pub new_authority: Pubkey,
+}
+
 #[error_code]
 pub enum ErrorCode {
     #[msg("Market has already been resolved")]
@@ -458,6 +932,93 @@ pub enum ErrorCode {
     #[msg("Invalid payout calculation")]
     InvalidPayout,
     
-    #[msg("Oracle price is stale")]
-    StaleOraclePrice,
+    #[msg("Price feed does not match the market's feed")]
+    WrongPriceFeed,
+    
+    #[msg("FX feed accounts are required for a local-currency threshold")]
+    MissingFxFeed,
+    
+    #[msg("FX feed does not convert between the feed and threshold currencies")]
+    WrongFxFeed,
+    
+    #[msg("Oracle feed is halted by its circuit breaker")]
+    OracleFeedHalted,
+    
+    #[msg("Token account is not the market vault")]
+    InvalidVault,
+    
+    #[msg("Unauthorized")]
+    Unauthorized,
+    
+    #[msg("Resolution fee exceeds the maximum")]
+    InvalidResolutionFee,
+    
+    #[msg("Oracle feed is deprecated")]
+    OracleFeedDeprecated,
+    
+    #[msg("Oracle fixing is not for the market's feed and expiry")]
+    WrongFixing,
+    
+    #[msg("Oracle reward pool and fee vault are required to pay the resolution fee")]
+    MissingOracleFeeAccounts,
+    
+    #[msg("Market has a fixing at expiry and must be resolved against it")]
+    FixingAvailable,
+    
+    #[msg("Market can only be voided after the grace period following expiry")]
+    VoidTooEarly,
+    
+    #[msg("Expiry time must be a multiple of the oracle history interval")]
+    UnalignedExpiryTime,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn expired_market() -> PredictionMarket {
+        PredictionMarket {
+            market_id: 1,
+            threshold_price: 250,
+            expiry_time: 1_000_000,
+            yes_pool: 300,
+            no_pool: 100,
+            resolution_fee_bps: RESOLUTION_FEE_BPS,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_void_market_without_fixing_refunds_stakes() {
+        let mut market = expired_market();
+        let both_sides = UserPosition { yes_shares: 200, no_shares: 50, ..Default::default() };
+        let no_side = UserPosition { no_shares: 50, ..Default::default() };
+        
+        // The fixing at expiry was missed: nothing settles until the grace period is over
+        assert!(market.void(market.expiry_time + VOID_GRACE_PERIOD - 1).is_err());
+        assert!(!market.resolved);
+        
+        market.void(market.expiry_time + VOID_GRACE_PERIOD).unwrap();
+        assert!(market.resolved && market.voided);
+        assert!(market.void(market.expiry_time + VOID_GRACE_PERIOD).is_err());
+        
+        // Every position gets its stake back, whichever side it was on
+        assert_eq!(market.payout(&both_sides).unwrap(), 250);
+        assert_eq!(market.payout(&no_side).unwrap(), 50);
+        assert!(market.payout(&UserPosition::default()).is_err());
+    }
+
+    #[test]
+    fn test_resolved_market_pays_winning_side() {
+        let mut market = expired_market();
+        market.resolved = true;
+        market.outcome = true;
+        market.resolution_fee = 4;
+        
+        let winner = UserPosition { yes_shares: 150, ..Default::default() };
+        let loser = UserPosition { no_shares: 100, ..Default::default() };
+        assert_eq!(market.payout(&winner).unwrap(), 198);
+        assert!(market.payout(&loser).is_err());
+        assert!(market.void(market.expiry_time + VOID_GRACE_PERIOD).is_err());
+    }
 }
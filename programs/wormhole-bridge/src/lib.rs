@@ -28,6 +28,8 @@ pub mod afrifutures_bridge {
         bridge.total_aff_bridged = 0;
         bridge.total_messages = 0;
         bridge.paused = false;
+        bridge.pending_authority = Pubkey::default();
+        bridge.guardian = Pubkey::default();
         
         msg!("Afrifutures Bridge initialized");
         msg!("Authority: {}", bridge.authority);
@@ -203,11 +205,13 @@ pub mod afrifutures_bridge {
         Ok(())
     }
 
-    /// Pause/unpause bridge (admin only)
+    /// Pause/unpause bridge (admin only; the guardian can pause but not unpause)
     pub fn set_pause(ctx: Context<SetPause>, paused: bool) -> Result<()> {
         let bridge = &mut ctx.accounts.bridge_state;
+        let signer = ctx.accounts.authority.key();
+        let is_guardian = paused && bridge.guardian != Pubkey::default() && signer == bridge.guardian;
         require!(
-            ctx.accounts.authority.key() == bridge.authority,
+            signer == bridge.authority || is_guardian,
             BridgeError::Unauthorized
         );
         
@@ -217,6 +221,38 @@ pub mod afrifutures_bridge {
         Ok(())
     }
 
+    /// Set the guardian key allowed to pause the bridge in an emergency (admin only)
+    pub fn set_guardian(ctx: Context<UpdateAuthority>, guardian: Pubkey) -> Result<()> {
+        ctx.accounts.bridge_state.guardian = guardian;
+        
+        msg!("Guardian set: {}", guardian);
+        Ok(())
+    }
+
+    /// Nominate a new authority, which must accept before it takes over (admin only)
+    pub fn propose_authority(ctx: Context<UpdateAuthority>, new_authority: Pubkey) -> Result<()> {
+        ctx.accounts.bridge_state.pending_authority = new_authority;
+        
+        msg!("Authority transfer proposed to {}", new_authority);
+        Ok(())
+    }
+
+    /// Complete an authority transfer (pending authority only)
+    pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+        let bridge = &mut ctx.accounts.bridge_state;
+        let previous = bridge.authority;
+        bridge.authority = bridge.pending_authority;
+        bridge.pending_authority = Pubkey::default();
+        
+        emit!(AuthorityTransferred {
+            previous,
+            new_authority: bridge.authority,
+        });
+        
+        msg!("Authority transferred to {}", bridge.authority);
+        Ok(())
+    }
+
     /// Withdraw fees (admin only)
     pub fn withdraw_fees(
         ctx: Context<WithdrawFees>,
@@ -271,7 +307,9 @@ pub struct BridgeState {
     pub total_aff_bridged: u64,      // 8
     pub total_messages: u64,         // 8
     pub paused: bool,                // 1
-    // Total: 93 bytes + 8 byte discriminator = 101 bytes
+    pub pending_authority: Pubkey,   // 32 - Nominated by propose_authority, default if none
+    pub guardian: Pubkey,            // 32 - May pause but not unpause, default if none
+    // Total: 157 bytes + 8 byte discriminator = 165 bytes
 }
 
 // Contexts
@@ -281,7 +319,7 @@ pub struct Initialize<'info> {
     #[account(
         init,
         payer = authority,
-        space = 8 + 32 + 32 + 4 + 8 + 8 + 8 + 1 + 64, // Extra space for future use
+        space = 8 + 32 + 32 + 4 + 8 + 8 + 8 + 1 + 32 + 32 + 64, // Extra space for future use
         seeds = [b"bridge"],
         bump
     )]
@@ -340,6 +378,32 @@ pub struct SetPause<'info> {
     pub authority: Signer<'info>,
 }
 
+#[derive(Accounts)]
+pub struct UpdateAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [b"bridge"],
+        bump,
+        has_one = authority @ BridgeError::Unauthorized
+    )]
+    pub bridge_state: Account<'info, BridgeState>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [b"bridge"],
+        bump,
+        constraint = bridge_state.pending_authority == pending_authority.key() @ BridgeError::Unauthorized
+    )]
+    pub bridge_state: Account<'info, BridgeState>,
+    
+    pub pending_authority: Signer<'info>,
+}
+
 #[derive(Accounts)]
 pub struct WithdrawFees<'info> {
     #[account(mut, seeds = [b"bridge"], bump)]
@@ -375,6 +439,12 @@ pub struct MessageSent {
     pub timestamp: i64,
 }
 
+#[event]
+pub struct AuthorityTransferred {
+    pub previous: Pubkey,
+    pub new_authority: Pubkey,
+}
+
 // Errors
 
 #[error_code]